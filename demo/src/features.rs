@@ -0,0 +1,31 @@
+pub const FEATURES: &str = r#"{
+    "type": "FeatureCollection",
+    "features": [
+        {
+            "type": "Feature",
+            "properties": { "name": "Luxembourg", "kind": "city", "population": 134697 },
+            "geometry": { "type": "Point", "coordinates": [6.1296, 49.8153] }
+        },
+        {
+            "type": "Feature",
+            "properties": { "name": "Cologne", "kind": "city", "population": 1084831 },
+            "geometry": { "type": "Point", "coordinates": [6.9603, 50.9375] }
+        },
+        {
+            "type": "Feature",
+            "properties": { "name": "Rhine (Cologne - Mainz)", "kind": "river" },
+            "geometry": {
+                "type": "LineString",
+                "coordinates": [[6.9603, 50.9375], [7.0982, 50.7374], [7.5886, 50.3569], [7.6053, 50.0782], [8.2473, 49.9929]]
+            }
+        },
+        {
+            "type": "Feature",
+            "properties": { "name": "Eifel", "kind": "region" },
+            "geometry": {
+                "type": "Polygon",
+                "coordinates": [[[6.1, 50.6], [7.0, 50.6], [7.3, 50.2], [6.6, 49.9], [6.1, 50.1], [6.1, 50.6]]]
+            }
+        }
+    ]
+}"#;
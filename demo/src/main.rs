@@ -1,9 +1,14 @@
 use dioxus::prelude::*;
 use dioxus_leaflet::{
-    Color, LatLng, Map, MapOptions, MapPosition, Marker, PathOptions, Polygon, Polyline, Popup,
-    TileLayer,
+    BaseLayer, Circle, CircleMarker, ClusterIcon, Color, Feature, FeatureCollection, FeatureGroup,
+    FeatureGroupHandle, GeoJson, LatLng, LatLngBounds, LayersControl, LeafletError, Map, MapHandle,
+    MapOptions, MapPosition, Marker, MarkerClusterGroup, MarkerContent, MarkerIcon, Overlay,
+    PathOptions, PointLayer, Polygon, Polyline, Popup, Rectangle, SvgOverlay, TileLayer,
+    TileLayerOptions, Tooltip, TooltipDirection, TooltipOptions, WmsLayer, WmsOptions,
 };
+use std::collections::HashMap;
 
+mod features;
 mod jersey;
 
 const CSS: Asset = asset!("/assets/demo.scss");
@@ -26,15 +31,58 @@ fn App() -> Element {
         ]]
     });
 
-    let options = use_signal(|| MapOptions::default().with_tile_layer(TileLayer::openstreetmap()));
+    let options = use_signal(|| MapOptions::default().without_tile_layer());
+
+    let base_layer = use_signal(|| "Streets".to_string());
+
+    let railways = use_signal(|| TileLayerOptions {
+        max_zoom: 19,
+        detect_retina: true,
+        ..TileLayerOptions::new(
+            "https://{s}.tiles.openrailwaymap.org/standard/{z}/{x}/{y}.png",
+            "&copy; <a href=\"https://www.openrailwaymap.org/\">OpenRailwayMap</a>",
+        )
+    });
+
+    let mut map = use_signal(|| None::<MapHandle>);
+
+    let mut position = use_signal(|| MapPosition::new(51.505, -0.09, 5.0));
+
+    let mut jersey_hovered = use_signal(|| false);
+
+    let mut clicked = use_signal(|| None::<LatLng>);
+
+    let mut route_open = use_signal(|| false);
+
+    let mut markers_visible = use_signal(|| true);
+
+    // Points spread on a grid around Paris, to show marker clustering
+    let deliveries = use_signal(|| {
+        (0..400)
+            .map(|i| {
+                let (row, col) = ((i / 20) as f64, (i % 20) as f64);
+                let pos = LatLng::new(48.7 + row * 0.02, 2.1 + col * 0.025);
+                (format!("Delivery #{i}"), pos)
+            })
+            .collect::<Vec<_>>()
+    });
+
+    let mut marker_group = use_signal(|| None::<FeatureGroupHandle>);
+
+    let features = use_signal(|| {
+        features::FEATURES
+            .parse::<FeatureCollection>()
+            .expect("demo GeoJSON is valid")
+    });
 
     rsx! {
         document::Style { href: CSS }
         Map {
-            initial_position: MapPosition::new(51.505, -0.09, 5.0),
-            options: options(),
+            position,
+            options,
             on_click: move |pos: LatLng| {
                 info!("Map clicked at: {:?}", pos);
+                clicked.set(Some(pos));
                 if let Some(marker) = markers.write().last_mut() {
                     marker.2 = pos;
                 }
@@ -42,13 +90,98 @@ fn App() -> Element {
             on_move: move |pos: MapPosition| {
                 info!("Map moved to: {:?}", pos);
             },
-            for marker in markers() {
-                Marker {
-                    coordinate: marker.2,
-                    Popup {
-                        b { "{marker.0}"}
-                        br {}
-                        "{marker.1}"
+            on_ready: move |handle: MapHandle| map.set(Some(handle)),
+            on_error: move |e: LeafletError| error!("Map failed: {e}"),
+            LayersControl {
+                base: base_layer,
+                BaseLayer {
+                    name: "Streets",
+                    TileLayer { layer: TileLayerOptions::openstreetmap() }
+                }
+                BaseLayer {
+                    name: "Satellite",
+                    TileLayer { layer: TileLayerOptions::satellite() }
+                }
+                BaseLayer {
+                    name: "Topographic",
+                    TileLayer { layer: TileLayerOptions::opentopomap() }
+                }
+                BaseLayer {
+                    name: "Dark",
+                    TileLayer { layer: TileLayerOptions::carto_dark_matter() }
+                }
+                BaseLayer {
+                    name: "Terrestris (WMS)",
+                    WmsLayer {
+                        url: "https://ows.terrestris.de/osm/service",
+                        options: WmsOptions {
+                            format: "image/png".to_string(),
+                            attribution: "&copy; terrestris, OpenStreetMap contributors".to_string(),
+                            ..WmsOptions::new("OSM-WMS")
+                        },
+                    }
+                }
+                Overlay {
+                    name: "Railways",
+                    TileLayer { layer: railways, opacity: Some(0.8), z_index: Some(2) }
+                }
+                Overlay {
+                    name: "Storm",
+                    SvgOverlay {
+                        bounds: LatLngBounds::new(LatLng::new(49.0, -4.5), LatLng::new(50.5, -1.0)),
+                        view_box: "0 0 100 100",
+                        opacity: 0.6,
+                        on_click: move |pos: LatLng| info!("Storm clicked at: {:?}", pos),
+                        circle { cx: 50, cy: 50, r: 48, fill: "#7fb3d5" }
+                        circle { cx: 50, cy: 50, r: 30, fill: "#2e86c1" }
+                        circle { cx: 50, cy: 50, r: 12, fill: "#1b4f72" }
+                    }
+                }
+            }
+            if let Some(pos) = clicked() {
+                Popup {
+                    position: Some(pos),
+                    "Clicked at {pos.lat:.4}, {pos.lng:.4}"
+                }
+            }
+            FeatureGroup {
+                visible: markers_visible,
+                on_click: move |pos: LatLng| info!("Capital clicked at: {:?}", pos),
+                on_ready: move |handle: FeatureGroupHandle| marker_group.set(Some(handle)),
+                for (i, marker) in markers().into_iter().enumerate() {
+                    Marker {
+                        coordinate: marker.2,
+                        icon: Some(MarkerIcon::colored(Color::from_rgb8(0xc0, 0x39, 0x2b))),
+                        draggable: true,
+                        on_drag_end: move |pos: LatLng| {
+                            if let Some(marker) = markers.write().get_mut(i) {
+                                marker.2 = pos;
+                            }
+                        },
+                        Tooltip {
+                            options: TooltipOptions {
+                                permanent: Some(true),
+                                direction: Some(TooltipDirection::Right),
+                                ..Default::default()
+                            },
+                            "{marker.0}"
+                        }
+                        Popup {
+                            b { "{marker.0}"}
+                            br {}
+                            "{marker.1}"
+                        }
+                    }
+                }
+            }
+            // Marker drawn from RSX, kept up to date with the map position
+            Marker {
+                coordinate: LatLng::new(50.8503, 4.3517),
+                MarkerContent {
+                    anchor: Some((0, 12)),
+                    div { class: "demo-badge",
+                        span { class: "demo-badge-dot" }
+                        "Zoom {position().zoom:.0}"
                     }
                 }
             }
@@ -59,7 +192,77 @@ fn App() -> Element {
                     weight: 5,
                     ..Default::default()
                 },
-                Popup { "Route connecting capitals" }
+                Popup {
+                    open: route_open,
+                    on_close: move |_| info!("Route popup closed"),
+                    "Route connecting capitals"
+                }
+            }
+            Circle {
+                center: LatLng::new(50.8503, 4.3517),
+                radius: 50_000.0,
+                options: PathOptions {
+                    color: Color::new([1., 0., 1.]),
+                    fill_color: Color::new([1., 0., 1.]),
+                    ..Default::default()
+                },
+                Popup { "50 km around Brussels" }
+            }
+            CircleMarker {
+                center: LatLng::new(52.3676, 4.9041),
+                radius: 10.0,
+                Popup { "Amsterdam" }
+            }
+            Rectangle {
+                bounds: LatLngBounds::new(LatLng::new(47.27, 5.87), LatLng::new(55.06, 15.04)),
+                options: PathOptions {
+                    color: Color::new([0., 0.5, 0.]),
+                    fill_opacity: 0.05,
+                    ..Default::default()
+                },
+                Popup { "Bounding box of Germany" }
+            }
+            MarkerClusterGroup {
+                cluster_icon: move |count: usize| {
+                    let size = if count < 10 { 30 } else if count < 100 { 40 } else { 50 };
+                    ClusterIcon {
+                        html: count.to_string(),
+                        class_name: Some("demo-cluster".to_string()),
+                        icon_size: Some((size, size)),
+                    }
+                },
+                on_cluster_click: move |data: Vec<HashMap<String, String>>| {
+                    info!("Cluster with {} deliveries clicked", data.len());
+                },
+                for (name, pos) in deliveries() {
+                    Marker {
+                        coordinate: pos,
+                        custom_data: HashMap::from([("name".to_string(), name.clone())]),
+                        Popup { "{name}" }
+                    }
+                }
+            }
+            GeoJson {
+                data: features,
+                style: move |feature: &Feature| {
+                    let color = match feature.property("kind").and_then(|k| k.as_str()) {
+                        Some("river") => Color::new([0., 0., 1.]),
+                        _ => Color::new([0.5, 0.3, 0.]),
+                    };
+                    PathOptions {
+                        color,
+                        fill_color: color,
+                        ..Default::default()
+                    }
+                },
+                point_to_layer: move |_: &Feature| PointLayer::CircleMarker {
+                    radius: 6.0,
+                    options: PathOptions::default(),
+                },
+                filter: move |feature: &Feature| feature.property("kind").is_some(),
+                on_feature_click: move |properties: serde_json::Map<String, serde_json::Value>| {
+                    info!("Feature clicked: {:?}", properties.get("name"));
+                },
             }
             Polygon {
                 coordinates: vec![vec![Vec::from(&jersey::JERSEY_BORDER)]],
@@ -67,8 +270,12 @@ fn App() -> Element {
                     color: Color::new([1., 1., 0.]),
                     fill: true,
                     fill_color: Color::new([1., 1., 0.]),
+                    fill_opacity: if jersey_hovered() { 0.6 } else { 0.2 },
                     ..Default::default()
                 },
+                on_mouseover: move |_| jersey_hovered.set(true),
+                on_mouseout: move |_| jersey_hovered.set(false),
+                Tooltip { "Jersey" }
                 Popup {
                     b { "Jersey" }
                     br {}
@@ -97,6 +304,37 @@ fn App() -> Element {
             },
             "Add Marker"
         }
+        button {
+            onclick: move |_| markers_visible.toggle(),
+            if markers_visible() { "Hide Markers" } else { "Show Markers" }
+        }
+        button {
+            onclick: move |_| async move {
+                if let Some(group) = marker_group()
+                    && let Some(map) = map()
+                    && let Ok(Some(bounds)) = group.bounds().await
+                {
+                    _ = map.fit_bounds(bounds.pad(0.2)).await;
+                }
+            },
+            "Fit Markers"
+        }
+        button {
+            onclick: move |_| route_open.toggle(),
+            if route_open() { "Hide Route Info" } else { "Show Route Info" }
+        }
+        button {
+            onclick: move |_| position.set(MapPosition::new(51.505, -0.09, 5.0)),
+            "Reset View"
+        }
+        button {
+            onclick: move |_| async move {
+                if let Some(map) = map() {
+                    _ = map.fly_to(MapPosition::new(52.52, 13.4, 10.0)).await;
+                }
+            },
+            "Fly to Berlin"
+        }
         button {
             onclick: move |_| {
                 // for mut m in markers.iter_mut() {
@@ -108,6 +346,10 @@ fn App() -> Element {
             },
             "Switch Types"
         }
+        p { "Base layer: {base_layer}" }
+        p {
+            "Center: {position().coordinates.lat:.4}, {position().coordinates.lng:.4} (zoom {position().zoom})"
+        }
     }
 }
 
@@ -1 +1,1864 @@
-async function p(){let t=window.L;while(!t)await h(100),t=window.L;return t}function h(t){return new Promise((o)=>setTimeout(o,t))}var s=new Map,c=new Map,P=new Map;async function d(t){let o=s.get(t);if(!o){let e=P.get(t);if(!e)e=new Promise((r)=>{c.set(t,r)}),P.set(t,e);o=await e}return o}async function a(t,o,e){let r=await p(),n=s.get(t)??r.map(`dioxus-leaflet-map-${t}`,{zoomControl:e.zoom_control,scrollWheelZoom:e.scroll_wheel_zoom,doubleClickZoom:e.double_click_zoom,touchZoom:e.touch_zoom,dragging:e.dragging,keyboard:e.keyboard,attributionControl:e.attribution_control});if(n.setView(o.coordinates,o.zoom),r.tileLayer(e.tile_layer.url,{attribution:e.tile_layer.attribution,maxZoom:e.tile_layer.max_zoom,subdomains:e.tile_layer.subdomains}).addTo(n),s.set(t,n),c.has(t))c.get(t)(n),c.delete(t),P.delete(t);await h(100),n.invalidateSize()}function E(t){s.delete(t)}async function b(t,o){await p();let e=await d(t);if(!e)throw Error(`Map with id ${t} not found when setting onClick handler`);e.on("click",async(r)=>{try{await o([r.latlng.lat,r.latlng.lng])}catch(n){console.error("Error in on_map_click callback:",n)}})}async function O(t,o){await p();let e=await d(t);if(!e)throw Error(`Map with id ${t} not found when setting onMove handler`);e.on("move",async()=>{let r=e.getCenter(),n=e.getZoom();try{await o([r.lat,r.lng,n])}catch(f){console.error("Error in map_on_move callback:",f)}})}var I=new Map;function x(t){return I.get(t)}async function v(t,o,e,r){let n=await p(),f=await d(t);if(!f)throw Error(`Map with id ${t} not found when updating polygon ${o}`);let u=I.get(o)??n.polygon([]).addTo(f);I.set(o,u),u.setLatLngs(e),u.setStyle(r);let w=g(o);if(w)u.unbindPopup(),u.bindPopup(w.body,w.options)}async function B(t,o){let e=await p();if(!await d(t))throw Error(`Map with id ${t} not found when deleting polygon ${o}`);I.delete(o)}var l=new Map;function g(t){return l.get(t)}async function A(t,o,e){let r=await p(),n=`dioxus-leaflet-popup-${o}`,f=document.getElementById(n);if(!f)throw Error(`Popup body element with id ${n} not found when updating popup for object ${t}`);l.set(t,{body:f,options:e});let u=i(t)??x(t);if(u)u.unbindPopup(),u.bindPopup(f,e)}var L=new Map;function i(t){return L.get(t)}async function N(t,o,e,r){let n=await p(),f=await d(t);if(!f)throw Error(`Map with id ${t} not found when updating marker ${o}`);let u=L.get(o)??n.marker([0,0]).addTo(f);if(L.set(o,u),u.setLatLng(e),r)u.setIcon(n.icon(r));let w=g(o);if(w)u.unbindPopup(),u.bindPopup(w.body,w.options)}async function Q(t,o){let e=await p(),r=await d(t);if(!r)throw Error(`Map with id ${t} not found when deleting marker ${o}`);let n=L.get(o);if(!n)throw Error(`Marker with id ${o} not found when deleting`);r.removeLayer(n),L.delete(o)}var y=new Map;function Y(t){return y.get(t)}async function Z(t,o,e,r){let n=await p(),f=await d(t);if(!f)throw Error(`Map with id ${t} not found when updating polyline ${o}`);let u=y.get(o)??n.polyline([]).addTo(f);y.set(o,u),u.setLatLngs(e),u.setStyle(r);let w=g(o);if(w)u.unbindPopup(),u.bindPopup(w.body,w.options)}async function F(t,o){let e=await p();if(!await d(t))throw Error(`Map with id ${t} not found when deleting polyline ${o}`);let n=y.get(o);if(n)n.remove(),y.delete(o)}export{A as update_popup,Z as update_polyline,v as update_polygon,N as update_marker,a as update_map,O as on_map_move,b as on_map_click,g as get_popup,Y as get_polyline,x as get_polygon,i as get_marker,d as get_map,F as delete_polyline,B as delete_polygon,Q as delete_marker,E as delete_map};
+// js_utils/src/util.ts
+// Message of the exception thrown by each call from Rust, which only learns that the call threw
+const _js_errors = new Map();
+
+// Set once Leaflet failed to load, so that nothing keeps waiting for it
+let _leaflet_unavailable = false;
+let _leaflet_fallback = null;
+
+async function setup() {
+    let l = window.L;
+    while (!l) {
+        if (_leaflet_unavailable) {
+            throw new Error("Leaflet is not available");
+        }
+        await wait(100);
+        l = window.L;
+    }
+    return l;
+}
+
+async function is_leaflet_loaded() {
+    return !!window.L;
+}
+
+async function wait_for_leaflet(timeout_ms) {
+    const deadline = Date.now() + timeout_ms;
+    while (!window.L) {
+        if (Date.now() >= deadline) {
+            return false;
+        }
+        await wait(100);
+    }
+    return true;
+}
+
+async function load_leaflet_fallback(files) {
+    _leaflet_fallback ??= new Promise((resolve) => {
+        const link = document.createElement("link");
+        link.rel = "stylesheet";
+        link.href = files.css;
+        if (files.css_integrity) {
+            link.integrity = files.css_integrity;
+            link.crossOrigin = "anonymous";
+        }
+        document.head.appendChild(link);
+
+        const script = document.createElement("script");
+        script.src = files.js;
+        if (files.js_integrity) {
+            script.integrity = files.js_integrity;
+            script.crossOrigin = "anonymous";
+        }
+        script.onload = () => resolve();
+        script.onerror = () => resolve();
+        document.head.appendChild(script);
+    });
+    await _leaflet_fallback;
+}
+
+async function load_leaflet(timeout_ms, fallback, call) {
+    return recording_errors(call, async () => {
+        if (await wait_for_leaflet(timeout_ms)) {
+            return true;
+        }
+
+        if (fallback) {
+            console.warn(`Leaflet did not load within ${timeout_ms} ms, trying ${fallback.js}`);
+            await load_leaflet_fallback(fallback);
+            if (await wait_for_leaflet(timeout_ms)) {
+                return true;
+            }
+        }
+
+        _leaflet_unavailable = true;
+        return false;
+    });
+}
+
+// Runs the body of a function called from Rust, keeping the message of its exception for `take_js_error`
+async function recording_errors(call, body) {
+    try {
+        return await body();
+    } catch (e) {
+        _js_errors.set(call, e instanceof Error ? e.message : String(e));
+        throw e;
+    }
+}
+
+async function take_js_error(call) {
+    const message = _js_errors.get(call) ?? null;
+    _js_errors.delete(call);
+    return message;
+}
+
+async function wait(ms) {
+    return new Promise((resolve) => setTimeout(resolve, ms));
+}
+
+function to_latlng_bounds(l, bounds) {
+    return l.latLngBounds(bounds.south_west, bounds.north_east);
+}
+
+// js_utils/src/layer.ts
+const _layers = new Map();
+const _handlers = new Map();
+const _waiting = new Map();
+
+// Maps and layer groups that child layers can be added to
+
+const _containers = new Map();
+const _container_waiting = new Map();
+
+// Marker cluster groups cluster layers added together at once, in chunks with `chunkedLoading`
+
+const _pending_layers = new Map();
+
+function get_layer(layer_id) {
+    return _layers.get(layer_id);
+}
+
+async function layer_exists(layer_id) {
+    return _layers.has(layer_id);
+}
+
+async function wait_for_layer(layer_id) {
+    const layer = _layers.get(layer_id);
+    if (layer) {
+        return layer;
+    }
+
+    return new Promise((resolve) => {
+        const waiting = _waiting.get(layer_id) ?? [];
+        waiting.push(resolve);
+        _waiting.set(layer_id, waiting);
+    });
+}
+
+function register_layer(layer_id, layer) {
+    _layers.set(layer_id, layer);
+
+    // Resolve any pending lookups
+    const waiting = _waiting.get(layer_id);
+    if (waiting) {
+        _waiting.delete(layer_id);
+        waiting.forEach((resolve) => resolve(layer));
+    }
+
+    // Attach handlers registered before the layer was created
+    const handlers = _handlers.get(layer_id);
+    if (handlers) {
+        for (const [event, handler] of handlers) {
+            listen(layer, event, handler);
+        }
+    }
+}
+
+// Layers with handlers keep their mouse events from also reaching the map, as markers always do
+function listen(layer, event, handler) {
+    layer.options.bubblingMouseEvents = false;
+    layer.on(event, handler);
+}
+
+function unregister_layer(layer_id) {
+    _layers.delete(layer_id);
+    _handlers.delete(layer_id);
+}
+
+// Removing a layer from the map alone would leave it in its group, which adds it back when shown,
+// so deleted layers are removed through their container
+function get_container(container_id) {
+    return _containers.get(container_id);
+}
+
+async function wait_for_container(container_id) {
+    const container = _containers.get(container_id);
+    if (container) {
+        return container;
+    }
+
+    return new Promise((resolve) => {
+        const waiting = _container_waiting.get(container_id) ?? [];
+        waiting.push(resolve);
+        _container_waiting.set(container_id, waiting);
+    });
+}
+
+function register_container(container_id, container) {
+    _containers.set(container_id, container);
+
+    const waiting = _container_waiting.get(container_id);
+    if (waiting) {
+        _container_waiting.delete(container_id);
+        waiting.forEach((resolve) => resolve(container));
+    }
+}
+
+function unregister_container(container_id) {
+    _containers.delete(container_id);
+    _pending_layers.delete(container_id);
+}
+
+function add_to_container(container_id, container, layer) {
+    if (!("addLayers" in container)) {
+        container.addLayer(layer);
+        return;
+    }
+
+    // Layers created in the same render are batched, and added to the group registered by then
+    let pending = _pending_layers.get(container_id);
+    if (!pending) {
+        pending = [];
+        _pending_layers.set(container_id, pending);
+        setTimeout(() => {
+            const layers = _pending_layers.get(container_id);
+            _pending_layers.delete(container_id);
+            const group = _containers.get(container_id);
+            if (layers && group) {
+                group.addLayers(layers);
+            }
+        }, 0);
+    }
+    pending.push(layer);
+}
+
+function remove_from_container(container_id, layer) {
+    const pending = _pending_layers.get(container_id);
+    const index = pending?.indexOf(layer) ?? -1;
+    if (index >= 0) {
+        pending.splice(index, 1);
+    }
+    get_container(container_id)?.removeLayer(layer);
+}
+
+async function on_layer_event(layer_id, event, callback, call) {
+    return recording_errors(call, async () => {
+        const handler = async (e) => {
+            // Drag start/end events carry no position, fall back to the layer's own
+            const latlng = e.latlng ?? e.target.getLatLng();
+            try {
+                await callback([latlng.lat, latlng.lng]);
+            } catch (error) {
+                console.error(`Error in ${event} callback:`, error);
+            }
+        };
+
+        let handlers = _handlers.get(layer_id);
+        if (!handlers) {
+            handlers = new Map();
+            _handlers.set(layer_id, handlers);
+        }
+
+        const layer = _layers.get(layer_id);
+        const previous = handlers.get(event);
+        if (layer && previous) {
+            layer.off(event, previous);
+        }
+
+        handlers.set(event, handler);
+        if (layer) {
+            listen(layer, event, handler);
+        }
+    });
+}
+
+// js_utils/src/tile_layer.ts
+const _tile_layers = new Map();
+const _tile_layer_configs = new Map();
+const _wms_states = new Map();
+
+function create_tile_layer(l, layer) {
+    return l.tileLayer(layer.url, {
+        // Leaflet fills any other placeholder of the URL from the options
+        ...layer.url_variables,
+        attribution: layer.attribution,
+        minZoom: layer.min_zoom,
+        maxZoom: layer.max_zoom,
+        maxNativeZoom: layer.max_native_zoom ?? undefined,
+        subdomains: layer.subdomains,
+        tileSize: layer.tile_size,
+        zoomOffset: layer.zoom_offset,
+        tms: layer.tms,
+        opacity: layer.opacity,
+        zIndex: layer.z_index,
+        detectRetina: layer.detect_retina,
+        errorTileUrl: layer.error_tile_url ?? undefined,
+        bounds: layer.bounds ? l.latLngBounds(layer.bounds.south_west, layer.bounds.north_east) : undefined,
+        crossOrigin: layer.cross_origin ?? undefined,
+    });
+}
+
+async function update_tile_layer(parent_id, layer_id, layer, opacity, z_index, call) {
+    return recording_errors(call, async () => {
+        const l = await setup();
+        const container = await wait_for_container(parent_id);
+
+        // Opacity and z-index are updated in place, any other change replaces the layer
+        const config = JSON.stringify({ ...layer, opacity: null, z_index: null });
+        let tiles = _tile_layers.get(layer_id);
+        if (tiles && _tile_layer_configs.get(layer_id) !== config) {
+            container.removeLayer(tiles);
+            unregister_layer(layer_id);
+            tiles = undefined;
+        }
+
+        if (!tiles) {
+            tiles = create_tile_layer(l, layer).addTo(container);
+            _tile_layers.set(layer_id, tiles);
+            _tile_layer_configs.set(layer_id, config);
+            register_layer(layer_id, tiles);
+        }
+
+        tiles.setOpacity(opacity ?? layer.opacity);
+        tiles.setZIndex(z_index ?? layer.z_index);
+    });
+}
+
+function wms_params(options) {
+    return {
+        ...options.extra_params,
+        layers: options.layers,
+        styles: options.styles,
+        format: options.format,
+        transparent: options.transparent,
+        version: options.version,
+    };
+}
+
+function wms_crs(l, crs) {
+    if (crs === null) {
+        return undefined;
+    }
+    const found = (l.CRS)[crs.replace(":", "")];
+    if (!found) {
+        console.warn(`Unknown WMS CRS ${crs}, using the map's own`);
+    }
+    return found;
+}
+
+async function update_wms_layer(parent_id, layer_id, url, options, opacity, z_index, call) {
+    return recording_errors(call, async () => {
+        const l = await setup();
+        const container = await wait_for_container(parent_id);
+
+        // Leaflet can only change the request params of a WMS layer, and only by merging them,
+        // so a new CRS or attribution, or a removed extra param, replaces the layer
+        const params = wms_params(options);
+        const layer_options = JSON.stringify([options.crs, options.attribution]);
+        const extra_keys = Object.keys(options.extra_params);
+        let tiles = _tile_layers.get(layer_id);
+        const state = _wms_states.get(layer_id);
+        if (tiles && state && (state.layer_options !== layer_options || state.extra_keys.some((key) => !extra_keys.includes(key)))) {
+            container.removeLayer(tiles);
+            unregister_layer(layer_id);
+            tiles = undefined;
+        }
+
+        if (!tiles || !state) {
+            tiles = l.tileLayer.wms(url, {
+                ...params,
+                attribution: options.attribution,
+                crs: wms_crs(l, options.crs),
+            }).addTo(container);
+            _tile_layers.set(layer_id, tiles);
+            register_layer(layer_id, tiles);
+        } else {
+            if (state.url !== url) {
+                tiles.setUrl(url);
+            }
+            if (state.params !== JSON.stringify(params)) {
+                tiles.setParams(params);
+            }
+        }
+        _wms_states.set(layer_id, { url, params: JSON.stringify(params), layer_options, extra_keys });
+
+        tiles.setOpacity(opacity);
+        tiles.setZIndex(z_index);
+    });
+}
+
+async function delete_tile_layer(parent_id, layer_id, call) {
+    return recording_errors(call, async () => {
+        const tiles = _tile_layers.get(layer_id);
+        if (tiles) {
+            get_container(parent_id)?.removeLayer(tiles);
+            tiles.remove();
+            _tile_layers.delete(layer_id);
+            _tile_layer_configs.delete(layer_id);
+            _wms_states.delete(layer_id);
+            unregister_layer(layer_id);
+        }
+    });
+}
+
+// js_utils/src/map.ts
+const _maps = new Map();
+const _callbacks = new Map();
+const _promises = new Map();
+const _tiles = new Map();
+const _tile_configs = new Map();
+
+async function get_map(map_id) {
+    let map = _maps.get(map_id);
+    if (!map) {
+        let p = _promises.get(map_id);
+        if (!p) {
+            p = new Promise((resolve) => {
+                _callbacks.set(map_id, resolve);
+            });
+            _promises.set(map_id, p);
+        }
+        map = await p;
+    }
+    return map;
+}
+
+// Maps are looked up without waiting when controlled from a handle, so calls fail instead of
+// hanging once the map is removed
+function find_map(map_id) {
+    const map = _maps.get(map_id);
+    if (!map) {
+        throw new Error(`Map with id ${map_id} not found`);
+    }
+    return map;
+}
+
+async function map_exists(map_id) {
+    return _maps.has(map_id);
+}
+
+function map_crs(l, crs) {
+    if (typeof crs === "string") {
+        return l.CRS[crs];
+    }
+    const { a, b, c, d } = crs.Custom;
+    return l.Util.extend({}, l.CRS.Simple, { transformation: l.transformation(a, b, c, d) });
+}
+
+function toggle_handler(handler, enabled) {
+    if (enabled) {
+        handler.enable();
+    } else {
+        handler.disable();
+    }
+}
+
+// Applies the options to an existing map, only touching what changed
+function apply_map_options(l, map_id, map, options) {
+    toggle_handler(map.scrollWheelZoom, !!options.scroll_wheel_zoom);
+    toggle_handler(map.doubleClickZoom, !!options.double_click_zoom);
+    toggle_handler(map.touchZoom, !!options.touch_zoom);
+    toggle_handler(map.dragging, !!options.dragging);
+    toggle_handler(map.keyboard, !!options.keyboard);
+    toggle_handler(map.boxZoom, options.box_zoom);
+
+    // Read by Leaflet whenever they are used, so they can simply be replaced
+    Object.assign(map.options, {
+        maxBoundsViscosity: options.max_bounds_viscosity,
+        zoomSnap: options.zoom_snap,
+        zoomDelta: options.zoom_delta,
+        wheelPxPerZoomLevel: options.wheel_px_per_zoom_level,
+        inertia: options.inertia,
+        worldCopyJump: options.world_copy_jump,
+    });
+
+    // Undefined limits fall back to the ones of the layers
+    if (map.options.minZoom !== (options.min_zoom ?? undefined)) {
+        map.setMinZoom(options.min_zoom ?? undefined);
+    }
+    if (map.options.maxZoom !== (options.max_zoom ?? undefined)) {
+        map.setMaxZoom(options.max_zoom ?? undefined);
+    }
+    const max_bounds = options.max_bounds ? to_latlng_bounds(l, options.max_bounds) : undefined;
+    const current_bounds = map.options.maxBounds;
+    if (max_bounds ? !current_bounds || !max_bounds.equals(current_bounds) : current_bounds) {
+        map.setMaxBounds(max_bounds);
+    }
+
+    // Controls are managed here rather than by Leaflet, so that they can be added and removed later
+    const controls = map;
+    if (options.zoom_control && !controls.zoomControl) {
+        controls.zoomControl = l.control.zoom().addTo(map);
+    } else if (!options.zoom_control && controls.zoomControl) {
+        controls.zoomControl.remove();
+        controls.zoomControl = undefined;
+    }
+    if (options.attribution_control && !controls.attributionControl) {
+        controls.attributionControl = l.control.attribution().addTo(map);
+    } else if (!options.attribution_control && controls.attributionControl) {
+        controls.attributionControl.remove();
+        controls.attributionControl = undefined;
+    }
+
+    // Replace the tile layer from the options only when it changed
+    const tile_config = JSON.stringify(options.tile_layer);
+    if (_tile_configs.get(map_id) !== tile_config) {
+        _tiles.get(map_id)?.remove();
+        _tiles.delete(map_id);
+        if (options.tile_layer) {
+            _tiles.set(map_id, create_tile_layer(l, options.tile_layer).addTo(map));
+        }
+        _tile_configs.set(map_id, tile_config);
+    }
+}
+
+async function update_map_options(map_id, options, call) {
+    return recording_errors(call, async () => {
+        const l = await setup();
+        apply_map_options(l, map_id, find_map(map_id), options);
+    });
+}
+
+async function update_map(map_id, initial_position, options, call) {
+    return recording_errors(call, async () => {
+        const l = await setup();
+
+        const existing = _maps.get(map_id);
+        if (existing) {
+            apply_map_options(l, map_id, existing, options);
+            return;
+        }
+
+        // Options that can change later are applied right after
+        const map = l.map(`dioxus-leaflet-map-${map_id}`, {
+            zoomControl: false,
+            attributionControl: false,
+            crs: map_crs(l, options.crs),
+            minZoom: options.min_zoom ?? undefined,
+            maxZoom: options.max_zoom ?? undefined,
+            maxBounds: options.max_bounds ? to_latlng_bounds(l, options.max_bounds) : undefined,
+            preferCanvas: options.prefer_canvas,
+            zoomAnimation: options.zoom_animation,
+            fadeAnimation: options.fade_animation,
+        });
+        map.setView(initial_position.coordinates, initial_position.zoom);
+        apply_map_options(l, map_id, map, options);
+
+        _maps.set(map_id, map);
+        register_container(map_id, map);
+
+        // Resolve any pending promises
+        if (_callbacks.has(map_id)) {
+            const callback = _callbacks.get(map_id);
+            callback(map);
+            _callbacks.delete(map_id);
+            _promises.delete(map_id);
+        }
+
+        // Force resize to ensure proper display
+        await wait(100);
+        map.invalidateSize();
+    });
+}
+
+async function delete_map(map_id, call) {
+    return recording_errors(call, async () => {
+        _maps.delete(map_id);
+        _tiles.delete(map_id);
+        _tile_configs.delete(map_id);
+        unregister_container(map_id);
+    });
+}
+
+async function on_map_click(map_id, callback, call) {
+    return recording_errors(call, async () => {
+        await setup();
+        const map = await get_map(map_id);
+        if (!map) {
+            throw new Error(`Map with id ${map_id} not found when setting onClick handler`);
+        }
+
+        map.on("click", async (e) => {
+            try {
+                await callback([e.latlng.lat, e.latlng.lng]);
+            } catch (error) {
+                console.error("Error in on_map_click callback:", error);
+            }
+        });
+    });
+}
+
+async function on_map_move(map_id, callback, call) {
+    return recording_errors(call, async () => {
+        await setup();
+        const map = await get_map(map_id);
+        if (!map) {
+            throw new Error(`Map with id ${map_id} not found when setting onMove handler`);
+        }
+
+        map.on("move", async () => {
+            const center = map.getCenter();
+            const zoom = map.getZoom();
+            try {
+                await callback([center.lat, center.lng, zoom]);
+            } catch (error) {
+                console.error("Error in map_on_move callback:", error);
+            }
+        });
+    });
+}
+
+async function on_map_move_end(map_id, callback, call) {
+    return recording_errors(call, async () => {
+        await setup();
+        const map = await get_map(map_id);
+        if (!map) {
+            throw new Error(`Map with id ${map_id} not found when setting onMoveEnd handler`);
+        }
+
+        map.on("moveend", async () => {
+            const center = map.getCenter();
+            const zoom = map.getZoom();
+            try {
+                await callback([center.lat, center.lng, zoom]);
+            } catch (error) {
+                console.error("Error in on_map_move_end callback:", error);
+            }
+        });
+    });
+}
+
+async function set_map_view(map_id, position, call) {
+    return recording_errors(call, async () => {
+        const map = find_map(map_id);
+        map.setView(position.coordinates, position.zoom);
+    });
+}
+
+async function fly_map_to(map_id, position, call) {
+    return recording_errors(call, async () => {
+        const map = find_map(map_id);
+        map.flyTo(position.coordinates, position.zoom);
+    });
+}
+
+async function pan_map_to(map_id, coordinates, call) {
+    return recording_errors(call, async () => {
+        const map = find_map(map_id);
+        map.panTo(coordinates);
+    });
+}
+
+async function set_map_zoom(map_id, zoom, call) {
+    return recording_errors(call, async () => {
+        const map = find_map(map_id);
+        map.setZoom(zoom);
+    });
+}
+
+async function zoom_map_in(map_id, call) {
+    return recording_errors(call, async () => {
+        const map = find_map(map_id);
+        map.zoomIn();
+    });
+}
+
+async function zoom_map_out(map_id, call) {
+    return recording_errors(call, async () => {
+        const map = find_map(map_id);
+        map.zoomOut();
+    });
+}
+
+async function fit_map_bounds(map_id, bounds, call) {
+    return recording_errors(call, async () => {
+        const l = await setup();
+        const map = find_map(map_id);
+        map.fitBounds(to_latlng_bounds(l, bounds));
+    });
+}
+
+async function invalidate_map_size(map_id, call) {
+    return recording_errors(call, async () => {
+        const map = find_map(map_id);
+        map.invalidateSize();
+    });
+}
+
+async function get_map_center(map_id, call) {
+    return recording_errors(call, async () => {
+        const map = find_map(map_id);
+        const center = map.getCenter();
+        return [center.lat, center.lng];
+    });
+}
+
+async function get_map_zoom(map_id, call) {
+    return recording_errors(call, async () => {
+        const map = find_map(map_id);
+        return map.getZoom();
+    });
+}
+
+async function get_map_bounds(map_id, call) {
+    return recording_errors(call, async () => {
+        const map = find_map(map_id);
+        const bounds = map.getBounds();
+        return [bounds.getSouth(), bounds.getWest(), bounds.getNorth(), bounds.getEast()];
+    });
+}
+
+// js_utils/src/marker.ts
+const _markers = new Map();
+const _marker_data = new WeakMap();
+const _marker_icons = new Map();
+const _marker_contents = new Map();
+
+function get_marker(marker_id) {
+    return _markers.get(marker_id);
+}
+
+function get_marker_custom_data(marker) {
+    return _marker_data.get(marker);
+}
+
+async function update_marker(parent_id, marker_id, coordinate, icon, draggable, custom_data, call) {
+    return recording_errors(call, async () => {
+        const l = await setup();
+        const container = await wait_for_container(parent_id);
+
+        let marker = _markers.get(marker_id);
+        if (!marker) {
+            marker = l.marker(coordinate);
+            add_to_container(parent_id, container, marker);
+            _markers.set(marker_id, marker);
+            register_layer(marker_id, marker);
+        }
+
+        marker.setLatLng(coordinate);
+        // Content rendered from RSX replaces the icon
+        if (icon) {
+            _marker_icons.set(marker_id, icon);
+            if (!_marker_contents.has(marker_id)) {
+                marker.setIcon(l.icon(icon));
+            }
+        } else if (_marker_icons.delete(marker_id) && !_marker_contents.has(marker_id)) {
+            marker.setIcon(new l.Icon.Default());
+        }
+
+        if (custom_data) {
+            _marker_data.set(marker, custom_data);
+        } else {
+            _marker_data.delete(marker);
+        }
+
+        // Markers waiting to be added to a cluster group have no drag handler yet, it reads the option once added
+        marker.options.draggable = draggable;
+        if (draggable) {
+            marker.dragging?.enable();
+        } else {
+            marker.dragging?.disable();
+        }
+    });
+}
+
+async function delete_marker(parent_id, marker_id, call) {
+    return recording_errors(call, async () => {
+        const l = await setup();
+
+        const marker = _markers.get(marker_id);
+        if (!marker) {
+            throw new Error(`Marker with id ${marker_id} not found when deleting`);
+        }
+
+        remove_from_container(parent_id, marker);
+        marker.remove();
+        _markers.delete(marker_id);
+        _marker_icons.delete(marker_id);
+        unregister_layer(marker_id);
+    });
+}
+async function update_marker_content(marker_id, options, call) {
+    return recording_errors(call, async () => {
+        const l = await setup();
+
+        // Leaflet moves the content into the icon element, so keep a reference to it
+        const id = `dioxus-leaflet-marker-${marker_id}-content`;
+        const content = _marker_contents.get(marker_id) ?? document.getElementById(id);
+        if (!content) {
+            throw new Error(`Marker content element with id ${id} not found when updating marker ${marker_id}`);
+        }
+        _marker_contents.set(marker_id, content);
+
+        const marker = await wait_for_layer(marker_id);
+        marker.setIcon(l.divIcon({ ...options, html: content }));
+    });
+}
+
+async function delete_marker_content(marker_id, call) {
+    return recording_errors(call, async () => {
+        const l = await setup();
+
+        _marker_contents.delete(marker_id);
+        const icon = _marker_icons.get(marker_id);
+        _markers.get(marker_id)?.setIcon(icon ? l.icon(icon) : new l.Icon.Default());
+    });
+}
+
+// js_utils/src/popup.ts
+const _popup_bodies = new Map();
+const _popups = new Map();
+const _popup_toggles = new Map();
+// Popups currently opened on the map at a position instead of bound to a layer
+const _standalone_popups = new Set();
+
+function get_popup_body(owner_id, popup_id) {
+    // Leaflet moves the body into its own container, so keep a reference to it
+    const id = `dioxus-leaflet-popup-${popup_id}`;
+    const body = _popup_bodies.get(popup_id) ?? document.getElementById(id);
+    if (!body) {
+        throw new Error(`Popup body element with id ${id} not found when updating popup for object ${owner_id}`);
+    }
+    _popup_bodies.set(popup_id, body);
+    return body;
+}
+
+async function get_or_create_popup(owner_id, popup_id, options) {
+    const l = await setup();
+    const body = get_popup_body(owner_id, popup_id);
+
+    let popup = _popups.get(popup_id);
+    if (!popup) {
+        popup = l.popup(options).setContent(body);
+        popup.on("add", () => notify_popup_toggle(popup_id, true));
+        popup.on("remove", () => notify_popup_toggle(popup_id, false));
+        _popups.set(popup_id, popup);
+    } else {
+        l.Util.setOptions(popup, options);
+        if (popup.isOpen()) {
+            popup.update();
+        }
+    }
+    return popup;
+}
+
+async function notify_popup_toggle(popup_id, open) {
+    const callback = _popup_toggles.get(popup_id);
+    if (!callback) {
+        return;
+    }
+    try {
+        await callback(open);
+    } catch (error) {
+        console.error(`Error in popup ${open ? "open" : "close"} callback:`, error);
+    }
+}
+
+async function update_popup(layer_id, popup_id, options, open, call) {
+    return recording_errors(call, async () => {
+        const popup = await get_or_create_popup(layer_id, popup_id, options);
+        if (_standalone_popups.delete(popup_id)) {
+            popup.close();
+        }
+
+        const layer = await wait_for_layer(layer_id);
+        if (layer.getPopup() !== popup) {
+            layer.bindPopup(popup);
+        }
+
+        if (open === true && !popup.isOpen()) {
+            layer.openPopup();
+        } else if (open === false && popup.isOpen()) {
+            layer.closePopup();
+        }
+    });
+}
+
+async function update_standalone_popup(map_id, popup_id, position, options, open, call) {
+    return recording_errors(call, async () => {
+        const map = await get_map(map_id);
+        if (!map) {
+            throw new Error(`Map with id ${map_id} not found when updating popup ${popup_id}`);
+        }
+
+        const popup = await get_or_create_popup(map_id, popup_id, options);
+        popup.setLatLng(position);
+
+        // Without a controlled state, a standalone popup opens once placed and then stays closed once closed by the user
+        const placed = !_standalone_popups.has(popup_id);
+        _standalone_popups.add(popup_id);
+        if ((open === true || (open === null && placed)) && !popup.isOpen()) {
+            popup.openOn(map);
+        } else if (open === false && popup.isOpen()) {
+            popup.close();
+        }
+    });
+}
+
+async function close_standalone_popup(popup_id, call) {
+    return recording_errors(call, async () => {
+        if (_standalone_popups.delete(popup_id)) {
+            _popups.get(popup_id)?.close();
+        }
+    });
+}
+
+async function on_popup_toggle(popup_id, callback, call) {
+    return recording_errors(call, async () => {
+        _popup_toggles.set(popup_id, callback);
+    });
+}
+
+async function delete_popup(owner_id, popup_id, call) {
+    return recording_errors(call, async () => {
+        // Closing below must not call back into the dropped component
+        _popup_toggles.delete(popup_id);
+        _popup_bodies.delete(popup_id);
+        _standalone_popups.delete(popup_id);
+
+        const popup = _popups.get(popup_id);
+        if (!popup) {
+            return;
+        }
+        _popups.delete(popup_id);
+
+        const layer = get_layer(owner_id);
+        if (layer?.getPopup() === popup) {
+            layer.unbindPopup();
+        }
+        popup.remove();
+    });
+}
+
+// js_utils/src/polygon.ts
+const _gons = new Map();
+
+function get_polygon(polygon_id) {
+    return _gons.get(polygon_id);
+}
+
+async function update_polygon(parent_id, polygon_id, coordinates, options, call) {
+    return recording_errors(call, async () => {
+        const l = await setup();
+        const container = await wait_for_container(parent_id);
+
+        let gon = _gons.get(polygon_id);
+        if (!gon) {
+            gon = l.polygon([]).addTo(container);
+            _gons.set(polygon_id, gon);
+            register_layer(polygon_id, gon);
+        }
+
+        gon.setLatLngs(coordinates);
+        gon.setStyle(options);
+    });
+}
+
+async function delete_polygon(parent_id, polygon_id, call) {
+    return recording_errors(call, async () => {
+        const l = await setup();
+
+        const gon = _gons.get(polygon_id);
+        if (gon) {
+            get_container(parent_id)?.removeLayer(gon);
+            gon.remove();
+            _gons.delete(polygon_id);
+            unregister_layer(polygon_id);
+        }
+    });
+}
+
+// js_utils/src/polyline.ts
+const _lines = new Map();
+
+function get_polyline(polyline_id) {
+    return _lines.get(polyline_id);
+}
+
+async function update_polyline(parent_id, polyline_id, coordinates, options, call) {
+    return recording_errors(call, async () => {
+        const l = await setup();
+        const container = await wait_for_container(parent_id);
+
+        let line = _lines.get(polyline_id);
+        if (!line) {
+            line = l.polyline([]).addTo(container);
+            _lines.set(polyline_id, line);
+            register_layer(polyline_id, line);
+        }
+
+        line.setLatLngs(coordinates);
+        line.setStyle(options);
+    });
+}
+
+async function delete_polyline(parent_id, polyline_id, call) {
+    return recording_errors(call, async () => {
+        const l = await setup();
+
+        const line = _lines.get(polyline_id);
+        if (line) {
+            get_container(parent_id)?.removeLayer(line);
+            line.remove();
+            _lines.delete(polyline_id);
+            unregister_layer(polyline_id);
+        }
+    });
+}
+
+// js_utils/src/circle.ts
+const _circles = new Map();
+
+function get_circle(circle_id) {
+    return _circles.get(circle_id);
+}
+
+async function update_circle(parent_id, circle_id, center, radius, options, call) {
+    return recording_errors(call, async () => {
+        const l = await setup();
+        const container = await wait_for_container(parent_id);
+
+        let circle = _circles.get(circle_id);
+        if (!circle) {
+            circle = l.circle(center, { radius }).addTo(container);
+            _circles.set(circle_id, circle);
+            register_layer(circle_id, circle);
+        }
+
+        circle.setLatLng(center);
+        circle.setRadius(radius);
+        circle.setStyle(options);
+    });
+}
+
+async function delete_circle(parent_id, circle_id, call) {
+    return recording_errors(call, async () => {
+        const l = await setup();
+
+        const circle = _circles.get(circle_id);
+        if (circle) {
+            get_container(parent_id)?.removeLayer(circle);
+            circle.remove();
+            _circles.delete(circle_id);
+            unregister_layer(circle_id);
+        }
+    });
+}
+
+// js_utils/src/circle_marker.ts
+const _circle_markers = new Map();
+
+function get_circle_marker(circle_marker_id) {
+    return _circle_markers.get(circle_marker_id);
+}
+
+async function update_circle_marker(parent_id, circle_marker_id, center, radius, options, call) {
+    return recording_errors(call, async () => {
+        const l = await setup();
+        const container = await wait_for_container(parent_id);
+
+        let marker = _circle_markers.get(circle_marker_id);
+        if (!marker) {
+            marker = l.circleMarker(center, { radius }).addTo(container);
+            _circle_markers.set(circle_marker_id, marker);
+            register_layer(circle_marker_id, marker);
+        }
+
+        marker.setLatLng(center);
+        marker.setRadius(radius);
+        marker.setStyle(options);
+    });
+}
+
+async function delete_circle_marker(parent_id, circle_marker_id, call) {
+    return recording_errors(call, async () => {
+        const l = await setup();
+
+        const marker = _circle_markers.get(circle_marker_id);
+        if (marker) {
+            get_container(parent_id)?.removeLayer(marker);
+            marker.remove();
+            _circle_markers.delete(circle_marker_id);
+            unregister_layer(circle_marker_id);
+        }
+    });
+}
+
+// js_utils/src/rectangle.ts
+const _rectangles = new Map();
+
+function get_rectangle(rectangle_id) {
+    return _rectangles.get(rectangle_id);
+}
+
+async function update_rectangle(parent_id, rectangle_id, bounds, options, call) {
+    return recording_errors(call, async () => {
+        const l = await setup();
+        const container = await wait_for_container(parent_id);
+
+        const latlng_bounds = to_latlng_bounds(l, bounds);
+        let rect = _rectangles.get(rectangle_id);
+        if (!rect) {
+            rect = l.rectangle(latlng_bounds).addTo(container);
+            _rectangles.set(rectangle_id, rect);
+            register_layer(rectangle_id, rect);
+        }
+
+        rect.setBounds(latlng_bounds);
+        rect.setStyle(options);
+    });
+}
+
+async function delete_rectangle(parent_id, rectangle_id, call) {
+    return recording_errors(call, async () => {
+        const l = await setup();
+
+        const rect = _rectangles.get(rectangle_id);
+        if (rect) {
+            get_container(parent_id)?.removeLayer(rect);
+            rect.remove();
+            _rectangles.delete(rectangle_id);
+            unregister_layer(rectangle_id);
+        }
+    });
+}
+
+// js_utils/src/tooltip.ts
+const _tooltip_bodies = new Map();
+
+async function update_tooltip(layer_id, tooltip_id, options, call) {
+    return recording_errors(call, async () => {
+        // Leaflet moves the body into its own container, so keep a reference to it
+        const id = `dioxus-leaflet-tooltip-${tooltip_id}`;
+        const body = _tooltip_bodies.get(tooltip_id) ?? document.getElementById(id);
+        if (!body) {
+            throw new Error(`Tooltip body element with id ${id} not found when updating tooltip for object ${layer_id}`);
+        }
+        _tooltip_bodies.set(tooltip_id, body);
+
+        const layer = await wait_for_layer(layer_id);
+        layer.unbindTooltip();
+        layer.bindTooltip(body, options);
+    });
+}
+
+async function delete_tooltip(layer_id, tooltip_id, call) {
+    return recording_errors(call, async () => {
+        _tooltip_bodies.delete(tooltip_id);
+        get_layer(layer_id)?.unbindTooltip();
+    });
+}
+
+// js_utils/src/geojson.ts
+const _geojson_layers = new Map();
+const _feature_clicks = new Map();
+
+function get_geojson(geojson_id) {
+    return _geojson_layers.get(geojson_id);
+}
+
+async function update_geojson(parent_id, geojson_id, items, call) {
+    return recording_errors(call, async () => {
+        const l = await setup();
+        const container = await wait_for_container(parent_id);
+
+        // Styles and point layers are computed on the Rust side, one entry per feature
+        let current;
+        const options = {
+            style: () => current?.style ?? {},
+            pointToLayer: (_feature, latlng) => {
+                const point = current?.point;
+                if (point?.type === "circle_marker") {
+                    return l.circleMarker(latlng, { ...point.options, radius: point.radius });
+                }
+                const marker = l.marker(latlng);
+                if (point?.icon) {
+                    marker.setIcon(l.icon(point.icon));
+                }
+                return marker;
+            },
+            onEachFeature: (feature, layer) => {
+                layer.on("click", () => notify_feature_click(geojson_id, feature));
+            },
+        };
+
+        let geojson = _geojson_layers.get(geojson_id);
+        if (!geojson) {
+            geojson = l.geoJSON(null, options).addTo(container);
+            _geojson_layers.set(geojson_id, geojson);
+            register_layer(geojson_id, geojson);
+        } else {
+            l.Util.setOptions(geojson, options);
+            geojson.clearLayers();
+        }
+
+        for (const item of items) {
+            current = item;
+            geojson.addData(item.feature);
+        }
+        current = undefined;
+    });
+}
+
+async function notify_feature_click(geojson_id, feature) {
+    const callback = _feature_clicks.get(geojson_id);
+    if (!callback) {
+        return;
+    }
+    try {
+        await callback((feature.properties ?? {}));
+    } catch (error) {
+        console.error("Error in feature click callback:", error);
+    }
+}
+
+async function on_geojson_feature_click(geojson_id, callback, call) {
+    return recording_errors(call, async () => {
+        _feature_clicks.set(geojson_id, callback);
+    });
+}
+
+async function delete_geojson(parent_id, geojson_id, call) {
+    return recording_errors(call, async () => {
+        const l = await setup();
+
+        _feature_clicks.delete(geojson_id);
+        const geojson = _geojson_layers.get(geojson_id);
+        if (geojson) {
+            get_container(parent_id)?.removeLayer(geojson);
+            geojson.remove();
+            _geojson_layers.delete(geojson_id);
+            unregister_layer(geojson_id);
+        }
+    });
+}
+
+// js_utils/src/layer_group.ts
+const _groups = new Map();
+
+function get_layer_group(group_id) {
+    return _groups.get(group_id);
+}
+
+async function update_layer_group(parent_id, group_id, feature_group, visible, call) {
+    return recording_errors(call, async () => {
+        const l = await setup();
+        const container = await wait_for_container(parent_id);
+
+        let group = _groups.get(group_id);
+        if (!group) {
+            group = feature_group ? l.featureGroup() : l.layerGroup();
+            _groups.set(group_id, group);
+            register_layer(group_id, group);
+            register_container(group_id, group);
+        }
+
+        if (visible && !container.hasLayer(group)) {
+            container.addLayer(group);
+        } else if (!visible && container.hasLayer(group)) {
+            container.removeLayer(group);
+        }
+    });
+}
+
+async function get_layer_group_bounds(group_id, call) {
+    return recording_errors(call, async () => {
+        const group = _groups.get(group_id);
+        if (!group?.getBounds) {
+            throw new Error(`Feature group with id ${group_id} not found when getting bounds`);
+        }
+
+        const bounds = group.getBounds();
+        if (!bounds.isValid()) {
+            return null;
+        }
+        return [bounds.getSouth(), bounds.getWest(), bounds.getNorth(), bounds.getEast()];
+    });
+}
+
+async function delete_layer_group(parent_id, group_id, call) {
+    return recording_errors(call, async () => {
+        const group = _groups.get(group_id);
+        if (group) {
+            get_container(parent_id)?.removeLayer(group);
+            group.remove();
+            _groups.delete(group_id);
+            unregister_layer(group_id);
+            unregister_container(group_id);
+        }
+    });
+}
+
+// js_utils/src/marker_cluster.ts
+// Leaflet.markercluster ships no typings in this package
+
+const _cluster_groups = new Map();
+const _cluster_group_options = new Map();
+const _cluster_icons = new Map();
+const _cluster_icon_requests = new Map();
+const _cluster_icon_callbacks = new Map();
+const _cluster_clicks = new Map();
+const _cluster_scripts = new Map();
+
+async function load_marker_cluster(l, script_url, script_integrity) {
+    if (l.markerClusterGroup) {
+        return;
+    }
+
+    // The plugin needs Leaflet to be loaded first, so it is only added once Leaflet is available
+    let loading = _cluster_scripts.get(script_url);
+    if (!loading) {
+        loading = new Promise((resolve, reject) => {
+            const script = document.createElement("script");
+            script.src = script_url;
+            if (script_integrity) {
+                script.integrity = script_integrity;
+                script.crossOrigin = "anonymous";
+            }
+            script.onload = () => resolve();
+            script.onerror = () => {
+                _cluster_scripts.delete(script_url);
+                reject(new Error(`Failed to load Leaflet.markercluster from ${script_url}`));
+            };
+            document.head.appendChild(script);
+        });
+        _cluster_scripts.set(script_url, loading);
+    }
+    await loading;
+}
+
+function create_cluster_group(l, group_id, options) {
+    const group = l.markerClusterGroup({
+        ...options,
+        iconCreateFunction: (cluster) => {
+            const count = cluster.getChildCount();
+            const icon = _cluster_icons.get(group_id)?.get(count);
+            if (icon) {
+                return icon;
+            }
+            request_cluster_icon(l, group_id, count);
+            return group._defaultIconCreateFunction(cluster);
+        },
+    });
+
+    group.on("clusterclick", async (e) => {
+        const callback = _cluster_clicks.get(group_id);
+        if (!callback) {
+            return;
+        }
+        const cluster = e.layer;
+        const data = cluster
+            .getAllChildMarkers()
+            .map((marker) => get_marker_custom_data(marker))
+            .filter((data) => data !== undefined);
+        try {
+            await callback(data);
+        } catch (error) {
+            console.error("Error in cluster click callback:", error);
+        }
+    });
+
+    return group;
+}
+
+async function request_cluster_icon(l, group_id, count) {
+    const callback = _cluster_icon_callbacks.get(group_id);
+    const requests = _cluster_icon_requests.get(group_id) ?? new Set();
+    if (!callback || requests.has(count)) {
+        return;
+    }
+    requests.add(count);
+    _cluster_icon_requests.set(group_id, requests);
+
+    try {
+        const options = await callback(count);
+        const icons = _cluster_icons.get(group_id) ?? new Map();
+        icons.set(count, l.divIcon(options));
+        _cluster_icons.set(group_id, icons);
+        _cluster_groups.get(group_id)?.refreshClusters();
+    } catch (error) {
+        console.error("Error in cluster icon callback:", error);
+    }
+}
+
+async function update_marker_cluster_group(parent_id, group_id, script_url, script_integrity, options, call) {
+    return recording_errors(call, async () => {
+        const l = await setup();
+        await load_marker_cluster(l, script_url, script_integrity);
+        const container = await wait_for_container(parent_id);
+
+        // Clustering options are only read when the group is created, so changing them rebuilds it
+        const key = JSON.stringify(options);
+        const previous = _cluster_groups.get(group_id);
+        if (previous && _cluster_group_options.get(group_id) === key) {
+            return;
+        }
+
+        const group = create_cluster_group(l, group_id, options);
+        if (previous) {
+            const layers = previous.getLayers();
+            container.removeLayer(previous);
+            group.addLayers(layers);
+        }
+        container.addLayer(group);
+
+        _cluster_groups.set(group_id, group);
+        _cluster_group_options.set(group_id, key);
+        register_layer(group_id, group);
+        register_container(group_id, group);
+    });
+}
+
+async function on_cluster_icon(group_id, callback, call) {
+    return recording_errors(call, async () => {
+        _cluster_icon_callbacks.set(group_id, callback);
+        _cluster_icons.delete(group_id);
+        _cluster_icon_requests.delete(group_id);
+        _cluster_groups.get(group_id)?.refreshClusters();
+    });
+}
+
+async function on_cluster_click(group_id, callback, call) {
+    return recording_errors(call, async () => {
+        _cluster_clicks.set(group_id, callback);
+    });
+}
+
+async function delete_marker_cluster_group(parent_id, group_id, call) {
+    return recording_errors(call, async () => {
+        _cluster_icon_callbacks.delete(group_id);
+        _cluster_clicks.delete(group_id);
+        _cluster_icons.delete(group_id);
+        _cluster_icon_requests.delete(group_id);
+
+        const group = _cluster_groups.get(group_id);
+        if (group) {
+            get_container(parent_id)?.removeLayer(group);
+            group.remove();
+            _cluster_groups.delete(group_id);
+            _cluster_group_options.delete(group_id);
+            unregister_layer(group_id);
+            unregister_container(group_id);
+        }
+    });
+}
+
+// js_utils/src/layers_control.ts
+const _layers_controls = new Map();
+const _layers_control_waiting = new Map();
+const _control_layers = new Map();
+
+async function wait_for_layers_control(control_id) {
+    const control = _layers_controls.get(control_id);
+    if (control) {
+        return control;
+    }
+
+    return new Promise((resolve) => {
+        const waiting = _layers_control_waiting.get(control_id) ?? [];
+        waiting.push(resolve);
+        _layers_control_waiting.set(control_id, waiting);
+    });
+}
+
+function current_selection(state) {
+    let base = null;
+    for (const [name, group] of state.bases) {
+        if (state.map.hasLayer(group)) {
+            base = name;
+            break;
+        }
+    }
+    const overlays = [...state.overlays]
+        .filter(([, group]) => state.map.hasLayer(group))
+        .map(([name]) => name);
+    return { base, overlays };
+}
+
+function apply_selection(state) {
+    state.applying = true;
+
+    // Keep exactly one base layer shown, the first one unless another was selected
+    const current = current_selection(state).base;
+    const base = [state.selected_base, current].find((name) => name !== null && state.bases.has(name))
+        ?? state.bases.keys().next().value;
+    for (const [name, group] of state.bases) {
+        if (name === base) {
+            state.map.addLayer(group);
+        } else {
+            state.map.removeLayer(group);
+        }
+    }
+
+    if (state.selected_overlays) {
+        for (const [name, group] of state.overlays) {
+            if (state.selected_overlays.includes(name)) {
+                state.map.addLayer(group);
+            } else {
+                state.map.removeLayer(group);
+            }
+        }
+    }
+
+    state.applying = false;
+    notify_layers_control_change(state);
+}
+
+async function notify_layers_control_change(state) {
+    if (state.applying || !state.on_change) {
+        return;
+    }
+    try {
+        await state.on_change(current_selection(state));
+    } catch (error) {
+        console.error("Error in layers control callback:", error);
+    }
+}
+
+// Leaflet only reads `collapsed` when the control is added, so its hover listeners are switched here the same way
+function set_layers_control_collapsed(l, state, collapsed) {
+    const control = state.control;
+    if (!!control.options.collapsed === collapsed) {
+        return;
+    }
+    control.options.collapsed = collapsed;
+
+    const container = control.getContainer();
+    const expand = (control)._expandSafely ?? control.expand;
+    if (collapsed) {
+        state.map.on("click", control.collapse, control);
+        if (container) {
+            l.DomEvent.on(container, { mouseenter: expand, mouseleave: control.collapse }, control);
+        }
+        control.collapse();
+    } else {
+        state.map.off("click", control.collapse, control);
+        if (container) {
+            l.DomEvent.off(container, { mouseenter: expand, mouseleave: control.collapse }, control);
+        }
+        control.expand();
+    }
+}
+
+async function update_layers_control(map_id, control_id, collapsed, call) {
+    return recording_errors(call, async () => {
+        const l = await setup();
+        const map = await get_map(map_id);
+
+        const existing = _layers_controls.get(control_id);
+        if (existing) {
+            set_layers_control_collapsed(l, existing, collapsed);
+            return;
+        }
+
+        const state = {
+            control: l.control.layers(undefined, undefined, { collapsed }).addTo(map),
+            map,
+            bases: new Map(),
+            overlays: new Map(),
+            selected_base: null,
+            selected_overlays: null,
+            applying: false,
+            on_layer_change: (e) => {
+                const { name, layer } = e;
+                if (state.bases.get(name) === layer || state.overlays.get(name) === layer) {
+                    notify_layers_control_change(state);
+                }
+            },
+        };
+        map.on("baselayerchange overlayadd overlayremove", state.on_layer_change);
+        _layers_controls.set(control_id, state);
+
+        const waiting = _layers_control_waiting.get(control_id);
+        if (waiting) {
+            _layers_control_waiting.delete(control_id);
+            waiting.forEach((resolve) => resolve(state));
+        }
+    });
+}
+
+async function set_layers_control_selection(control_id, base, overlays, call) {
+    return recording_errors(call, async () => {
+        const state = await wait_for_layers_control(control_id);
+        state.selected_base = base;
+        state.selected_overlays = overlays;
+        apply_selection(state);
+    });
+}
+
+async function on_layers_control_change(control_id, callback, call) {
+    return recording_errors(call, async () => {
+        const state = await wait_for_layers_control(control_id);
+        state.on_change = callback;
+        notify_layers_control_change(state);
+    });
+}
+
+async function update_control_layer(control_id, layer_id, name, overlay, call) {
+    return recording_errors(call, async () => {
+        const l = await setup();
+        const state = await wait_for_layers_control(control_id);
+
+        let entry = _control_layers.get(layer_id);
+        if (entry && entry.name === name && entry.overlay === overlay) {
+            return;
+        }
+
+        if (entry) {
+            // Renamed, list the group again under its new name
+            state.control.removeLayer(entry.group);
+            (entry.overlay ? state.overlays : state.bases).delete(entry.name);
+        } else {
+            const group = l.layerGroup();
+            entry = { control_id, name, overlay, group };
+            register_layer(layer_id, group);
+            register_container(layer_id, group);
+            // Without a selection from Rust, overlays are shown when added
+            if (overlay && !state.selected_overlays) {
+                state.map.addLayer(group);
+            }
+        }
+
+        entry.name = name;
+        entry.overlay = overlay;
+        _control_layers.set(layer_id, entry);
+        if (overlay) {
+            state.overlays.set(name, entry.group);
+            state.control.addOverlay(entry.group, name);
+        } else {
+            state.bases.set(name, entry.group);
+            state.control.addBaseLayer(entry.group, name);
+        }
+        apply_selection(state);
+    });
+}
+
+async function delete_control_layer(control_id, layer_id, call) {
+    return recording_errors(call, async () => {
+        const entry = _control_layers.get(layer_id);
+        const state = _layers_controls.get(control_id);
+        if (!entry) {
+            return;
+        }
+
+        _control_layers.delete(layer_id);
+        unregister_layer(layer_id);
+        unregister_container(layer_id);
+        entry.group.remove();
+        if (state) {
+            state.control.removeLayer(entry.group);
+            (entry.overlay ? state.overlays : state.bases).delete(entry.name);
+            apply_selection(state);
+        }
+    });
+}
+
+async function delete_layers_control(control_id, call) {
+    return recording_errors(call, async () => {
+        const state = _layers_controls.get(control_id);
+        if (state) {
+            state.map.off("baselayerchange overlayadd overlayremove", state.on_layer_change);
+            state.control.remove();
+            _layers_controls.delete(control_id);
+        }
+    });
+}
+
+// js_utils/src/image_overlay.ts
+// Leaflet has no setters for some options, so overlays are recreated when their `key` changes
+const _image_overlays = new Map();
+const _svg_elements = new Map();
+
+function leaflet_overlay_options(options) {
+    return {
+        opacity: options.opacity,
+        zIndex: options.z_index,
+        interactive: options.interactive,
+        className: options.class_name ?? undefined,
+        alt: options.alt ?? undefined,
+        crossOrigin: options.cross_origin ?? undefined,
+    };
+}
+
+function remove_image_overlay(parent_id, overlay_id) {
+    const entry = _image_overlays.get(overlay_id);
+    if (entry) {
+        get_container(parent_id)?.removeLayer(entry.overlay);
+        entry.overlay.remove();
+        _image_overlays.delete(overlay_id);
+    }
+}
+
+async function place_image_overlay(
+    parent_id,
+    overlay_id,
+    bounds,
+    options,
+    key,
+    create,
+) {
+    const l = await setup();
+    const container = await wait_for_container(parent_id);
+
+    const latlng_bounds = to_latlng_bounds(l, bounds);
+    key = JSON.stringify([key, options.interactive, options.class_name, options.alt, options.cross_origin]);
+    let entry = _image_overlays.get(overlay_id);
+    if (entry && entry.key !== key) {
+        remove_image_overlay(parent_id, overlay_id);
+        entry = undefined;
+    }
+    if (!entry) {
+        entry = { overlay: create(l, latlng_bounds).addTo(container), key };
+        _image_overlays.set(overlay_id, entry);
+        register_layer(overlay_id, entry.overlay);
+    }
+
+    const overlay = entry.overlay;
+    overlay.setBounds(latlng_bounds);
+    overlay.setOpacity(options.opacity);
+    overlay.setZIndex(options.z_index);
+    return overlay;
+}
+
+async function update_image_overlay(parent_id, overlay_id, url, bounds, options, call) {
+    return recording_errors(call, async () => {
+        const overlay = await place_image_overlay(parent_id, overlay_id, bounds, options, "image",
+            (l, bounds) => l.imageOverlay(url, bounds, leaflet_overlay_options(options)));
+        // Changing the URL keeps the overlay, so frames can be swapped without flickering
+        if ((overlay)._url !== url) {
+            overlay.setUrl(url);
+        }
+    });
+}
+
+async function update_video_overlay(parent_id, overlay_id, urls, bounds, options, video, call) {
+    return recording_errors(call, async () => {
+        const overlay = await place_image_overlay(parent_id, overlay_id, bounds, options, JSON.stringify(["video", urls]),
+            (l, bounds) => l.videoOverlay(urls, bounds, {
+                ...leaflet_overlay_options(options),
+                autoplay: video.autoplay,
+                loop: video.looped,
+                muted: video.muted,
+                keepAspectRatio: video.keep_aspect_ratio,
+            }));
+
+        const element = overlay.getElement();
+        if (element) {
+            if (video.autoplay && !element.autoplay && element.paused) {
+                element.play().catch((error) => console.warn("Video overlay could not start playing:", error));
+            }
+            element.autoplay = video.autoplay;
+            element.loop = video.looped;
+            element.muted = video.muted;
+            element.style.objectFit = video.keep_aspect_ratio ? "contain" : "fill";
+        }
+    });
+}
+
+async function update_svg_overlay(parent_id, overlay_id, bounds, options, call) {
+    return recording_errors(call, async () => {
+        // Leaflet moves the element into its own pane, and out of the document while hidden, so keep a reference to it
+        const id = `dioxus-leaflet-image-overlay-${overlay_id}`;
+        const element = _svg_elements.get(overlay_id) ?? document.getElementById(id);
+        if (!element) {
+            throw new Error(`SVG element with id ${id} not found when updating overlay ${overlay_id}`);
+        }
+        _svg_elements.set(overlay_id, element);
+
+        await place_image_overlay(parent_id, overlay_id, bounds, options, "svg",
+            (l, bounds) => l.svgOverlay(element, bounds, leaflet_overlay_options(options)));
+    });
+}
+
+async function delete_image_overlay(parent_id, overlay_id, call) {
+    return recording_errors(call, async () => {
+        remove_image_overlay(parent_id, overlay_id);
+        _svg_elements.delete(overlay_id);
+        unregister_layer(overlay_id);
+    });
+}
+
+export {
+  add_to_container,
+  close_standalone_popup,
+  create_tile_layer,
+  delete_circle,
+  delete_circle_marker,
+  delete_control_layer,
+  delete_geojson,
+  delete_image_overlay,
+  delete_layer_group,
+  delete_layers_control,
+  delete_map,
+  delete_marker,
+  delete_marker_cluster_group,
+  delete_marker_content,
+  delete_polygon,
+  delete_polyline,
+  delete_popup,
+  delete_rectangle,
+  delete_tile_layer,
+  delete_tooltip,
+  fit_map_bounds,
+  fly_map_to,
+  get_circle,
+  get_circle_marker,
+  get_container,
+  get_geojson,
+  get_layer,
+  get_layer_group,
+  get_layer_group_bounds,
+  get_map,
+  get_map_bounds,
+  get_map_center,
+  get_map_zoom,
+  get_marker,
+  get_marker_custom_data,
+  get_polygon,
+  get_polyline,
+  get_rectangle,
+  invalidate_map_size,
+  is_leaflet_loaded,
+  layer_exists,
+  load_leaflet,
+  map_exists,
+  on_cluster_click,
+  on_cluster_icon,
+  on_geojson_feature_click,
+  on_layer_event,
+  on_layers_control_change,
+  on_map_click,
+  on_map_move,
+  on_map_move_end,
+  on_popup_toggle,
+  pan_map_to,
+  recording_errors,
+  register_container,
+  register_layer,
+  remove_from_container,
+  set_layers_control_selection,
+  set_map_view,
+  set_map_zoom,
+  setup,
+  take_js_error,
+  to_latlng_bounds,
+  unregister_container,
+  unregister_layer,
+  update_circle,
+  update_circle_marker,
+  update_control_layer,
+  update_geojson,
+  update_image_overlay,
+  update_layer_group,
+  update_layers_control,
+  update_map,
+  update_map_options,
+  update_marker,
+  update_marker_cluster_group,
+  update_marker_content,
+  update_polygon,
+  update_polyline,
+  update_popup,
+  update_rectangle,
+  update_standalone_popup,
+  update_svg_overlay,
+  update_tile_layer,
+  update_tooltip,
+  update_video_overlay,
+  update_wms_layer,
+  wait,
+  wait_for_container,
+  wait_for_layer,
+  zoom_map_in,
+  zoom_map_out,
+};
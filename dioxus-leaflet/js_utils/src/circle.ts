@@ -0,0 +1,41 @@
+import { setup, recording_errors } from "./util";
+import type { L, Id, Call } from "./types";
+import { get_container, register_layer, unregister_layer, wait_for_container } from "./layer";
+
+const _circles = new Map<Id, L.Circle>();
+
+export function get_circle(circle_id: Id): L.Circle | undefined {
+    return _circles.get(circle_id);
+}
+
+export async function update_circle(parent_id: Id, circle_id: Id, center: L.LatLngExpression, radius: number, options: L.PathOptions, call: Call) {
+    return recording_errors(call, async () => {
+        const l = await setup();
+        const container = await wait_for_container(parent_id);
+
+        let circle = _circles.get(circle_id);
+        if (!circle) {
+            circle = l.circle(center, { radius }).addTo(container);
+            _circles.set(circle_id, circle);
+            register_layer(circle_id, circle);
+        }
+
+        circle.setLatLng(center);
+        circle.setRadius(radius);
+        circle.setStyle(options);
+    });
+}
+
+export async function delete_circle(parent_id: Id, circle_id: Id, call: Call) {
+    return recording_errors(call, async () => {
+        const l = await setup();
+
+        const circle = _circles.get(circle_id);
+        if (circle) {
+            get_container(parent_id)?.removeLayer(circle);
+            circle.remove();
+            _circles.delete(circle_id);
+            unregister_layer(circle_id);
+        }
+    });
+}
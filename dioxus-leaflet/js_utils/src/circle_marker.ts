@@ -0,0 +1,41 @@
+import { setup, recording_errors } from "./util";
+import type { L, Id, Call } from "./types";
+import { get_container, register_layer, unregister_layer, wait_for_container } from "./layer";
+
+const _circle_markers = new Map<Id, L.CircleMarker>();
+
+export function get_circle_marker(circle_marker_id: Id): L.CircleMarker | undefined {
+    return _circle_markers.get(circle_marker_id);
+}
+
+export async function update_circle_marker(parent_id: Id, circle_marker_id: Id, center: L.LatLngExpression, radius: number, options: L.PathOptions, call: Call) {
+    return recording_errors(call, async () => {
+        const l = await setup();
+        const container = await wait_for_container(parent_id);
+
+        let marker = _circle_markers.get(circle_marker_id);
+        if (!marker) {
+            marker = l.circleMarker(center, { radius }).addTo(container);
+            _circle_markers.set(circle_marker_id, marker);
+            register_layer(circle_marker_id, marker);
+        }
+
+        marker.setLatLng(center);
+        marker.setRadius(radius);
+        marker.setStyle(options);
+    });
+}
+
+export async function delete_circle_marker(parent_id: Id, circle_marker_id: Id, call: Call) {
+    return recording_errors(call, async () => {
+        const l = await setup();
+
+        const marker = _circle_markers.get(circle_marker_id);
+        if (marker) {
+            get_container(parent_id)?.removeLayer(marker);
+            marker.remove();
+            _circle_markers.delete(circle_marker_id);
+            unregister_layer(circle_marker_id);
+        }
+    });
+}
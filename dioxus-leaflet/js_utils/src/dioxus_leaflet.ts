@@ -1,5 +1,16 @@
 export * from "./map";
+export * from "./layer";
 export * from "./marker";
 export * from "./popup";
 export * from "./polygon";
 export * from "./polyline";
+export * from "./circle";
+export * from "./circle_marker";
+export * from "./rectangle";
+export * from "./tooltip";
+export * from "./geojson";
+export * from "./layer_group";
+export * from "./marker_cluster";
+export * from "./tile_layer";
+export * from "./layers_control";
+export * from "./image_overlay";
@@ -0,0 +1,86 @@
+import type { L, Id, Json, RustCallback, GeoJsonItem, Call } from "./types";
+import { setup, recording_errors } from "./util";
+import { get_container, register_layer, unregister_layer, wait_for_container } from "./layer";
+
+const _geojson_layers = new Map<Id, L.GeoJSON>();
+const _feature_clicks = new Map<Id, RustCallback<Json, void>>();
+
+export function get_geojson(geojson_id: Id): L.GeoJSON | undefined {
+    return _geojson_layers.get(geojson_id);
+}
+
+export async function update_geojson(parent_id: Id, geojson_id: Id, items: GeoJsonItem[], call: Call) {
+    return recording_errors(call, async () => {
+        const l = await setup();
+        const container = await wait_for_container(parent_id);
+
+        // Styles and point layers are computed on the Rust side, one entry per feature
+        let current: GeoJsonItem | undefined;
+        const options: L.GeoJSONOptions = {
+            style: () => current?.style ?? {},
+            pointToLayer: (_feature, latlng) => {
+                const point = current?.point;
+                if (point?.type === "circle_marker") {
+                    return l.circleMarker(latlng, { ...point.options, radius: point.radius });
+                }
+                const marker = l.marker(latlng);
+                if (point?.icon) {
+                    marker.setIcon(l.icon(point.icon));
+                }
+                return marker;
+            },
+            onEachFeature: (feature, layer) => {
+                layer.on("click", () => notify_feature_click(geojson_id, feature));
+            },
+        };
+
+        let geojson = _geojson_layers.get(geojson_id);
+        if (!geojson) {
+            geojson = l.geoJSON(null, options).addTo(container);
+            _geojson_layers.set(geojson_id, geojson);
+            register_layer(geojson_id, geojson);
+        } else {
+            l.Util.setOptions(geojson, options);
+            geojson.clearLayers();
+        }
+
+        for (const item of items) {
+            current = item;
+            geojson.addData(item.feature);
+        }
+        current = undefined;
+    });
+}
+
+async function notify_feature_click(geojson_id: Id, feature: GeoJSON.Feature) {
+    const callback = _feature_clicks.get(geojson_id);
+    if (!callback) {
+        return;
+    }
+    try {
+        await callback((feature.properties ?? {}) as Json);
+    } catch (error) {
+        console.error("Error in feature click callback:", error);
+    }
+}
+
+export async function on_geojson_feature_click(geojson_id: Id, callback: RustCallback<Json, void>, call: Call): Promise<void> {
+    return recording_errors(call, async () => {
+        _feature_clicks.set(geojson_id, callback);
+    });
+}
+
+export async function delete_geojson(parent_id: Id, geojson_id: Id, call: Call) {
+    return recording_errors(call, async () => {
+        const l = await setup();
+
+        _feature_clicks.delete(geojson_id);
+        const geojson = _geojson_layers.get(geojson_id);
+        if (geojson) {
+            get_container(parent_id)?.removeLayer(geojson);
+            geojson.remove();
+            _geojson_layers.delete(geojson_id);
+            unregister_layer(geojson_id);
+        }
+    });
+}
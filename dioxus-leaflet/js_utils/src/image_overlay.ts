@@ -0,0 +1,134 @@
+import { setup, to_latlng_bounds, recording_errors } from "./util";
+import type { L, Id, LatLngBounds, Call } from "./types";
+import { get_container, register_layer, unregister_layer, wait_for_container } from "./layer";
+
+interface OverlayOptions {
+    opacity: number,
+    z_index: number,
+    interactive: boolean,
+    class_name: string | null,
+    alt: string | null,
+    cross_origin: L.CrossOrigin | null,
+}
+
+interface VideoOptions {
+    autoplay: boolean,
+    looped: boolean,
+    muted: boolean,
+    keep_aspect_ratio: boolean,
+}
+
+type MediaOverlay = L.ImageOverlay | L.VideoOverlay | L.SVGOverlay;
+
+// Leaflet has no setters for some options, so overlays are recreated when their `key` changes
+const _image_overlays = new Map<Id, { overlay: MediaOverlay, key: string }>();
+const _svg_elements = new Map<Id, SVGElement>();
+
+function leaflet_overlay_options(options: OverlayOptions): L.ImageOverlayOptions {
+    return {
+        opacity: options.opacity,
+        zIndex: options.z_index,
+        interactive: options.interactive,
+        className: options.class_name ?? undefined,
+        alt: options.alt ?? undefined,
+        crossOrigin: options.cross_origin ?? undefined,
+    };
+}
+
+function remove_image_overlay(parent_id: Id, overlay_id: Id) {
+    const entry = _image_overlays.get(overlay_id);
+    if (entry) {
+        get_container(parent_id)?.removeLayer(entry.overlay);
+        entry.overlay.remove();
+        _image_overlays.delete(overlay_id);
+    }
+}
+
+async function place_image_overlay(
+    parent_id: Id,
+    overlay_id: Id,
+    bounds: LatLngBounds,
+    options: OverlayOptions,
+    key: string,
+    create: (l: typeof L, bounds: L.LatLngBounds) => MediaOverlay,
+): Promise<MediaOverlay> {
+    const l = await setup();
+    const container = await wait_for_container(parent_id);
+
+    const latlng_bounds = to_latlng_bounds(l, bounds);
+    key = JSON.stringify([key, options.interactive, options.class_name, options.alt, options.cross_origin]);
+    let entry = _image_overlays.get(overlay_id);
+    if (entry && entry.key !== key) {
+        remove_image_overlay(parent_id, overlay_id);
+        entry = undefined;
+    }
+    if (!entry) {
+        entry = { overlay: create(l, latlng_bounds).addTo(container), key };
+        _image_overlays.set(overlay_id, entry);
+        register_layer(overlay_id, entry.overlay);
+    }
+
+    const overlay = entry.overlay;
+    overlay.setBounds(latlng_bounds);
+    overlay.setOpacity(options.opacity);
+    overlay.setZIndex(options.z_index);
+    return overlay;
+}
+
+export async function update_image_overlay(parent_id: Id, overlay_id: Id, url: string, bounds: LatLngBounds, options: OverlayOptions, call: Call) {
+    return recording_errors(call, async () => {
+        const overlay = await place_image_overlay(parent_id, overlay_id, bounds, options, "image",
+            (l, bounds) => l.imageOverlay(url, bounds, leaflet_overlay_options(options))) as L.ImageOverlay;
+        // Changing the URL keeps the overlay, so frames can be swapped without flickering
+        if ((overlay as unknown as { _url: string })._url !== url) {
+            overlay.setUrl(url);
+        }
+    });
+}
+
+export async function update_video_overlay(parent_id: Id, overlay_id: Id, urls: string[], bounds: LatLngBounds, options: OverlayOptions, video: VideoOptions, call: Call) {
+    return recording_errors(call, async () => {
+        const overlay = await place_image_overlay(parent_id, overlay_id, bounds, options, JSON.stringify(["video", urls]),
+            (l, bounds) => l.videoOverlay(urls, bounds, {
+                ...leaflet_overlay_options(options),
+                autoplay: video.autoplay,
+                loop: video.looped,
+                muted: video.muted,
+                keepAspectRatio: video.keep_aspect_ratio,
+            })) as L.VideoOverlay;
+
+        const element = overlay.getElement();
+        if (element) {
+            if (video.autoplay && !element.autoplay && element.paused) {
+                element.play().catch((error) => console.warn("Video overlay could not start playing:", error));
+            }
+            element.autoplay = video.autoplay;
+            element.loop = video.looped;
+            element.muted = video.muted;
+            element.style.objectFit = video.keep_aspect_ratio ? "contain" : "fill";
+        }
+    });
+}
+
+export async function update_svg_overlay(parent_id: Id, overlay_id: Id, bounds: LatLngBounds, options: OverlayOptions, call: Call) {
+    return recording_errors(call, async () => {
+        // Leaflet moves the element into its own pane, and out of the document while hidden, so keep a reference to it
+        const id = `dioxus-leaflet-image-overlay-${overlay_id}`;
+        const element = _svg_elements.get(overlay_id) ?? document.getElementById(id) as SVGElement | null;
+        if (!element) {
+            throw new Error(`SVG element with id ${id} not found when updating overlay ${overlay_id}`);
+        }
+        _svg_elements.set(overlay_id, element);
+
+        await place_image_overlay(parent_id, overlay_id, bounds, options, "svg",
+            (l, bounds) => l.svgOverlay(element, bounds, leaflet_overlay_options(options)));
+    });
+}
+
+export async function delete_image_overlay(parent_id: Id, overlay_id: Id, call: Call) {
+    return recording_errors(call, async () => {
+        remove_image_overlay(parent_id, overlay_id);
+        _svg_elements.delete(overlay_id);
+        unregister_layer(overlay_id);
+    });
+}
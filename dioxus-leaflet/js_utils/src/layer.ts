@@ -0,0 +1,165 @@
+import type { L, Id, RustCallback, Call } from "./types";
+import { recording_errors } from "./util";
+
+const _layers = new Map<Id, L.Layer>();
+const _handlers = new Map<Id, Map<string, L.LeafletEventHandlerFn>>();
+const _waiting = new Map<Id, ((layer: L.Layer) => void)[]>();
+
+// Maps and layer groups that child layers can be added to
+type LayerContainer = L.Map | L.LayerGroup;
+const _containers = new Map<Id, LayerContainer>();
+const _container_waiting = new Map<Id, ((container: LayerContainer) => void)[]>();
+
+// Marker cluster groups cluster layers added together at once, in chunks with `chunkedLoading`
+interface BulkLayerGroup extends L.LayerGroup {
+    addLayers(layers: L.Layer[]): this;
+}
+const _pending_layers = new Map<Id, L.Layer[]>();
+
+export function get_layer(layer_id: Id): L.Layer | undefined {
+    return _layers.get(layer_id);
+}
+
+export async function layer_exists(layer_id: Id): Promise<boolean> {
+    return _layers.has(layer_id);
+}
+
+export async function wait_for_layer(layer_id: Id): Promise<L.Layer> {
+    const layer = _layers.get(layer_id);
+    if (layer) {
+        return layer;
+    }
+
+    return new Promise<L.Layer>((resolve) => {
+        const waiting = _waiting.get(layer_id) ?? [];
+        waiting.push(resolve);
+        _waiting.set(layer_id, waiting);
+    });
+}
+
+export function register_layer(layer_id: Id, layer: L.Layer) {
+    _layers.set(layer_id, layer);
+
+    // Resolve any pending lookups
+    const waiting = _waiting.get(layer_id);
+    if (waiting) {
+        _waiting.delete(layer_id);
+        waiting.forEach((resolve) => resolve(layer));
+    }
+
+    // Attach handlers registered before the layer was created
+    const handlers = _handlers.get(layer_id);
+    if (handlers) {
+        for (const [event, handler] of handlers) {
+            listen(layer, event, handler);
+        }
+    }
+}
+
+// Layers with handlers keep their mouse events from also reaching the map, as markers always do
+function listen(layer: L.Layer, event: string, handler: L.LeafletEventHandlerFn) {
+    (layer.options as L.InteractiveLayerOptions).bubblingMouseEvents = false;
+    layer.on(event, handler);
+}
+
+export function unregister_layer(layer_id: Id) {
+    _layers.delete(layer_id);
+    _handlers.delete(layer_id);
+}
+
+// Removing a layer from the map alone would leave it in its group, which adds it back when shown,
+// so deleted layers are removed through their container
+export function get_container(container_id: Id): LayerContainer | undefined {
+    return _containers.get(container_id);
+}
+
+export async function wait_for_container(container_id: Id): Promise<LayerContainer> {
+    const container = _containers.get(container_id);
+    if (container) {
+        return container;
+    }
+
+    return new Promise<LayerContainer>((resolve) => {
+        const waiting = _container_waiting.get(container_id) ?? [];
+        waiting.push(resolve);
+        _container_waiting.set(container_id, waiting);
+    });
+}
+
+export function register_container(container_id: Id, container: LayerContainer) {
+    _containers.set(container_id, container);
+
+    const waiting = _container_waiting.get(container_id);
+    if (waiting) {
+        _container_waiting.delete(container_id);
+        waiting.forEach((resolve) => resolve(container));
+    }
+}
+
+export function unregister_container(container_id: Id) {
+    _containers.delete(container_id);
+    _pending_layers.delete(container_id);
+}
+
+export function add_to_container(container_id: Id, container: LayerContainer, layer: L.Layer) {
+    if (!("addLayers" in container)) {
+        container.addLayer(layer);
+        return;
+    }
+
+    // Layers created in the same render are batched, and added to the group registered by then
+    let pending = _pending_layers.get(container_id);
+    if (!pending) {
+        pending = [];
+        _pending_layers.set(container_id, pending);
+        setTimeout(() => {
+            const layers = _pending_layers.get(container_id);
+            _pending_layers.delete(container_id);
+            const group = _containers.get(container_id) as BulkLayerGroup | undefined;
+            if (layers && group) {
+                group.addLayers(layers);
+            }
+        }, 0);
+    }
+    pending.push(layer);
+}
+
+export function remove_from_container(container_id: Id, layer: L.Layer) {
+    const pending = _pending_layers.get(container_id);
+    const index = pending?.indexOf(layer) ?? -1;
+    if (index >= 0) {
+        pending!.splice(index, 1);
+    }
+    get_container(container_id)?.removeLayer(layer);
+}
+
+export async function on_layer_event(layer_id: Id, event: string, callback: RustCallback<number[], void>, call: Call): Promise<void> {
+    return recording_errors(call, async () => {
+        const handler = async (e: L.LeafletEvent) => {
+            // Drag start/end events carry no position, fall back to the layer's own
+            const latlng = (e as L.LeafletMouseEvent).latlng ?? (e.target as L.Marker).getLatLng();
+            try {
+                await callback([latlng.lat, latlng.lng]);
+            } catch (error) {
+                console.error(`Error in ${event} callback:`, error);
+            }
+        };
+
+        let handlers = _handlers.get(layer_id);
+        if (!handlers) {
+            handlers = new Map();
+            _handlers.set(layer_id, handlers);
+        }
+
+        const layer = _layers.get(layer_id);
+        const previous = handlers.get(event);
+        if (layer && previous) {
+            layer.off(event, previous);
+        }
+
+        handlers.set(event, handler);
+        if (layer) {
+            listen(layer, event, handler);
+        }
+    });
+}
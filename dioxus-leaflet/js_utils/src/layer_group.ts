@@ -0,0 +1,58 @@
+import type { L, Id, Call } from "./types";
+import { setup, recording_errors } from "./util";
+import { get_container, register_container, register_layer, unregister_container, unregister_layer, wait_for_container } from "./layer";
+
+const _groups = new Map<Id, L.LayerGroup>();
+
+export function get_layer_group(group_id: Id): L.LayerGroup | undefined {
+    return _groups.get(group_id);
+}
+
+export async function update_layer_group(parent_id: Id, group_id: Id, feature_group: boolean, visible: boolean, call: Call) {
+    return recording_errors(call, async () => {
+        const l = await setup();
+        const container = await wait_for_container(parent_id);
+
+        let group = _groups.get(group_id);
+        if (!group) {
+            group = feature_group ? l.featureGroup() : l.layerGroup();
+            _groups.set(group_id, group);
+            register_layer(group_id, group);
+            register_container(group_id, group);
+        }
+
+        if (visible && !container.hasLayer(group)) {
+            container.addLayer(group);
+        } else if (!visible && container.hasLayer(group)) {
+            container.removeLayer(group);
+        }
+    });
+}
+
+export async function get_layer_group_bounds(group_id: Id, call: Call): Promise<number[] | null> {
+    return recording_errors(call, async () => {
+        const group = _groups.get(group_id) as L.FeatureGroup | undefined;
+        if (!group?.getBounds) {
+            throw new Error(`Feature group with id ${group_id} not found when getting bounds`);
+        }
+
+        const bounds = group.getBounds();
+        if (!bounds.isValid()) {
+            return null;
+        }
+        return [bounds.getSouth(), bounds.getWest(), bounds.getNorth(), bounds.getEast()];
+    });
+}
+
+export async function delete_layer_group(parent_id: Id, group_id: Id, call: Call) {
+    return recording_errors(call, async () => {
+        const group = _groups.get(group_id);
+        if (group) {
+            get_container(parent_id)?.removeLayer(group);
+            group.remove();
+            _groups.delete(group_id);
+            unregister_layer(group_id);
+            unregister_container(group_id);
+        }
+    });
+}
@@ -0,0 +1,237 @@
+import type { L, Id, Json, RustCallback, Call } from "./types";
+import { get_map } from "./map";
+import { setup, recording_errors } from "./util";
+import { register_container, register_layer, unregister_container, unregister_layer } from "./layer";
+
+interface LayersControl {
+    control: L.Control.Layers,
+    map: L.Map,
+    bases: Map<string, L.LayerGroup>,
+    overlays: Map<string, L.LayerGroup>,
+    // Selection requested from Rust, null when left to the user
+    selected_base: string | null,
+    selected_overlays: string[] | null,
+    applying: boolean,
+    on_layer_change: L.LeafletEventHandlerFn,
+    on_change?: RustCallback<Json, void>,
+}
+
+const _layers_controls = new Map<Id, LayersControl>();
+const _layers_control_waiting = new Map<Id, ((control: LayersControl) => void)[]>();
+const _control_layers = new Map<Id, { control_id: Id, name: string, overlay: boolean, group: L.LayerGroup }>();
+
+async function wait_for_layers_control(control_id: Id): Promise<LayersControl> {
+    const control = _layers_controls.get(control_id);
+    if (control) {
+        return control;
+    }
+
+    return new Promise<LayersControl>((resolve) => {
+        const waiting = _layers_control_waiting.get(control_id) ?? [];
+        waiting.push(resolve);
+        _layers_control_waiting.set(control_id, waiting);
+    });
+}
+
+function current_selection(state: LayersControl): { base: string | null, overlays: string[] } {
+    let base: string | null = null;
+    for (const [name, group] of state.bases) {
+        if (state.map.hasLayer(group)) {
+            base = name;
+            break;
+        }
+    }
+    const overlays = [...state.overlays]
+        .filter(([, group]) => state.map.hasLayer(group))
+        .map(([name]) => name);
+    return { base, overlays };
+}
+
+function apply_selection(state: LayersControl) {
+    state.applying = true;
+
+    // Keep exactly one base layer shown, the first one unless another was selected
+    const current = current_selection(state).base;
+    const base = [state.selected_base, current].find((name) => name !== null && state.bases.has(name))
+        ?? state.bases.keys().next().value;
+    for (const [name, group] of state.bases) {
+        if (name === base) {
+            state.map.addLayer(group);
+        } else {
+            state.map.removeLayer(group);
+        }
+    }
+
+    if (state.selected_overlays) {
+        for (const [name, group] of state.overlays) {
+            if (state.selected_overlays.includes(name)) {
+                state.map.addLayer(group);
+            } else {
+                state.map.removeLayer(group);
+            }
+        }
+    }
+
+    state.applying = false;
+    notify_layers_control_change(state);
+}
+
+async function notify_layers_control_change(state: LayersControl) {
+    if (state.applying || !state.on_change) {
+        return;
+    }
+    try {
+        await state.on_change(current_selection(state));
+    } catch (error) {
+        console.error("Error in layers control callback:", error);
+    }
+}
+
+// Leaflet only reads `collapsed` when the control is added, so its hover listeners are switched here the same way
+function set_layers_control_collapsed(l: typeof L, state: LayersControl, collapsed: boolean) {
+    const control = state.control;
+    if (!!control.options.collapsed === collapsed) {
+        return;
+    }
+    control.options.collapsed = collapsed;
+
+    const container = control.getContainer();
+    const expand = (control as unknown as { _expandSafely?: () => void })._expandSafely ?? control.expand;
+    if (collapsed) {
+        state.map.on("click", control.collapse, control);
+        if (container) {
+            l.DomEvent.on(container, { mouseenter: expand, mouseleave: control.collapse }, control);
+        }
+        control.collapse();
+    } else {
+        state.map.off("click", control.collapse, control);
+        if (container) {
+            l.DomEvent.off(container, { mouseenter: expand, mouseleave: control.collapse }, control);
+        }
+        control.expand();
+    }
+}
+
+export async function update_layers_control(map_id: Id, control_id: Id, collapsed: boolean, call: Call) {
+    return recording_errors(call, async () => {
+        const l = await setup();
+        const map = await get_map(map_id);
+
+        const existing = _layers_controls.get(control_id);
+        if (existing) {
+            set_layers_control_collapsed(l, existing, collapsed);
+            return;
+        }
+
+        const state: LayersControl = {
+            control: l.control.layers(undefined, undefined, { collapsed }).addTo(map),
+            map,
+            bases: new Map(),
+            overlays: new Map(),
+            selected_base: null,
+            selected_overlays: null,
+            applying: false,
+            on_layer_change: (e) => {
+                const { name, layer } = e as L.LayersControlEvent;
+                if (state.bases.get(name) === layer || state.overlays.get(name) === layer) {
+                    notify_layers_control_change(state);
+                }
+            },
+        };
+        map.on("baselayerchange overlayadd overlayremove", state.on_layer_change);
+        _layers_controls.set(control_id, state);
+
+        const waiting = _layers_control_waiting.get(control_id);
+        if (waiting) {
+            _layers_control_waiting.delete(control_id);
+            waiting.forEach((resolve) => resolve(state));
+        }
+    });
+}
+
+export async function set_layers_control_selection(control_id: Id, base: string | null, overlays: string[] | null, call: Call) {
+    return recording_errors(call, async () => {
+        const state = await wait_for_layers_control(control_id);
+        state.selected_base = base;
+        state.selected_overlays = overlays;
+        apply_selection(state);
+    });
+}
+
+export async function on_layers_control_change(control_id: Id, callback: RustCallback<Json, void>, call: Call): Promise<void> {
+    return recording_errors(call, async () => {
+        const state = await wait_for_layers_control(control_id);
+        state.on_change = callback;
+        notify_layers_control_change(state);
+    });
+}
+
+export async function update_control_layer(control_id: Id, layer_id: Id, name: string, overlay: boolean, call: Call) {
+    return recording_errors(call, async () => {
+        const l = await setup();
+        const state = await wait_for_layers_control(control_id);
+
+        let entry = _control_layers.get(layer_id);
+        if (entry && entry.name === name && entry.overlay === overlay) {
+            return;
+        }
+
+        if (entry) {
+            // Renamed, list the group again under its new name
+            state.control.removeLayer(entry.group);
+            (entry.overlay ? state.overlays : state.bases).delete(entry.name);
+        } else {
+            const group = l.layerGroup();
+            entry = { control_id, name, overlay, group };
+            register_layer(layer_id, group);
+            register_container(layer_id, group);
+            // Without a selection from Rust, overlays are shown when added
+            if (overlay && !state.selected_overlays) {
+                state.map.addLayer(group);
+            }
+        }
+
+        entry.name = name;
+        entry.overlay = overlay;
+        _control_layers.set(layer_id, entry);
+        if (overlay) {
+            state.overlays.set(name, entry.group);
+            state.control.addOverlay(entry.group, name);
+        } else {
+            state.bases.set(name, entry.group);
+            state.control.addBaseLayer(entry.group, name);
+        }
+        apply_selection(state);
+    });
+}
+
+export async function delete_control_layer(control_id: Id, layer_id: Id, call: Call) {
+    return recording_errors(call, async () => {
+        const entry = _control_layers.get(layer_id);
+        const state = _layers_controls.get(control_id);
+        if (!entry) {
+            return;
+        }
+
+        _control_layers.delete(layer_id);
+        unregister_layer(layer_id);
+        unregister_container(layer_id);
+        entry.group.remove();
+        if (state) {
+            state.control.removeLayer(entry.group);
+            (entry.overlay ? state.overlays : state.bases).delete(entry.name);
+            apply_selection(state);
+        }
+    });
+}
+
+export async function delete_layers_control(control_id: Id, call: Call) {
+    return recording_errors(call, async () => {
+        const state = _layers_controls.get(control_id);
+        if (state) {
+            state.map.off("baselayerchange overlayadd overlayremove", state.on_layer_change);
+            state.control.remove();
+            _layers_controls.delete(control_id);
+        }
+    });
+}
@@ -1,9 +1,13 @@
-import type { L, Id, MapOptions, MapPosition, RustCallback, Json } from "./types";
-import { setup, wait } from "./util";
+import type { L, Crs, Id, LatLngBounds, MapOptions, MapPosition, RustCallback, Json, Call } from "./types";
+import { setup, to_latlng_bounds, wait, recording_errors } from "./util";
+import { register_container, unregister_container } from "./layer";
+import { create_tile_layer } from "./tile_layer";
 
 const _maps = new Map<Id, L.Map>();
 const _callbacks = new Map<Id, (map: L.Map) => void>();
 const _promises = new Map<Id, Promise<L.Map>>();
+const _tiles = new Map<Id, L.TileLayer>();
+const _tile_configs = new Map<Id, string>();
 
 export async function get_map(map_id: Id): Promise<L.Map> {
     let map = _maps.get(map_id);
@@ -20,78 +24,287 @@ export async function get_map(map_id: Id): Promise<L.Map> {
     return map;
 }
 
-export async function update_map(map_id: Id, initial_position: MapPosition, options: MapOptions): Promise<void> {
-    const l = await setup();
+// Maps are looked up without waiting when controlled from a handle, so calls fail instead of
+// hanging once the map is removed
+function find_map(map_id: Id): L.Map {
+    const map = _maps.get(map_id);
+    if (!map) {
+        throw new Error(`Map with id ${map_id} not found`);
+    }
+    return map;
+}
 
-    // Initialize the map with options
-    const map = _maps.get(map_id) ?? l.map(`dioxus-leaflet-map-${map_id}`, {
-        zoomControl: options.zoom_control,
-        scrollWheelZoom: options.scroll_wheel_zoom,
-        doubleClickZoom: options.double_click_zoom,
-        touchZoom: options.touch_zoom,
-        dragging: options.dragging,
-        keyboard: options.keyboard,
-        attributionControl: options.attribution_control
-    });
+export async function map_exists(map_id: Id): Promise<boolean> {
+    return _maps.has(map_id);
+}
+
+function map_crs(l: typeof L, crs: Crs): L.CRS {
+    if (typeof crs === "string") {
+        return l.CRS[crs];
+    }
+    const { a, b, c, d } = crs.Custom;
+    return l.Util.extend({}, l.CRS.Simple, { transformation: l.transformation(a, b, c, d) });
+}
+
+function toggle_handler(handler: L.Handler, enabled: boolean) {
+    if (enabled) {
+        handler.enable();
+    } else {
+        handler.disable();
+    }
+}
 
-    map.setView(initial_position.coordinates, initial_position.zoom);
+// Applies the options to an existing map, only touching what changed
+function apply_map_options(l: typeof L, map_id: Id, map: L.Map, options: MapOptions) {
+    toggle_handler(map.scrollWheelZoom, !!options.scroll_wheel_zoom);
+    toggle_handler(map.doubleClickZoom, !!options.double_click_zoom);
+    toggle_handler(map.touchZoom, !!options.touch_zoom);
+    toggle_handler(map.dragging, !!options.dragging);
+    toggle_handler(map.keyboard, !!options.keyboard);
+    toggle_handler(map.boxZoom, options.box_zoom);
 
-    // Add tile layer
-    l.tileLayer(options.tile_layer.url, {
-        attribution: options.tile_layer.attribution,
-        maxZoom: options.tile_layer.max_zoom,
-        subdomains: options.tile_layer.subdomains
-    }).addTo(map);
+    // Read by Leaflet whenever they are used, so they can simply be replaced
+    Object.assign(map.options, {
+        maxBoundsViscosity: options.max_bounds_viscosity,
+        zoomSnap: options.zoom_snap,
+        zoomDelta: options.zoom_delta,
+        wheelPxPerZoomLevel: options.wheel_px_per_zoom_level,
+        inertia: options.inertia,
+        worldCopyJump: options.world_copy_jump,
+    });
 
-    _maps.set(map_id, map);
+    // Undefined limits fall back to the ones of the layers
+    if (map.options.minZoom !== (options.min_zoom ?? undefined)) {
+        map.setMinZoom(options.min_zoom ?? undefined as unknown as number);
+    }
+    if (map.options.maxZoom !== (options.max_zoom ?? undefined)) {
+        map.setMaxZoom(options.max_zoom ?? undefined as unknown as number);
+    }
+    const max_bounds = options.max_bounds ? to_latlng_bounds(l, options.max_bounds) : undefined;
+    const current_bounds = map.options.maxBounds as L.LatLngBounds | null | undefined;
+    if (max_bounds ? !current_bounds || !max_bounds.equals(current_bounds) : current_bounds) {
+        map.setMaxBounds(max_bounds);
+    }
 
-    // Resolve any pending promises
-    if (_callbacks.has(map_id)) {
-        const callback = _callbacks.get(map_id)!;
-        callback(map);
-        _callbacks.delete(map_id);
-        _promises.delete(map_id);
+    // Controls are managed here rather than by Leaflet, so that they can be added and removed later
+    const controls = map as { zoomControl?: L.Control.Zoom, attributionControl?: L.Control.Attribution };
+    if (options.zoom_control && !controls.zoomControl) {
+        controls.zoomControl = l.control.zoom().addTo(map);
+    } else if (!options.zoom_control && controls.zoomControl) {
+        controls.zoomControl.remove();
+        controls.zoomControl = undefined;
+    }
+    if (options.attribution_control && !controls.attributionControl) {
+        controls.attributionControl = l.control.attribution().addTo(map);
+    } else if (!options.attribution_control && controls.attributionControl) {
+        controls.attributionControl.remove();
+        controls.attributionControl = undefined;
     }
 
-    // Force resize to ensure proper display
-    await wait(100);
-    map.invalidateSize();
+    // Replace the tile layer from the options only when it changed
+    const tile_config = JSON.stringify(options.tile_layer);
+    if (_tile_configs.get(map_id) !== tile_config) {
+        _tiles.get(map_id)?.remove();
+        _tiles.delete(map_id);
+        if (options.tile_layer) {
+            _tiles.set(map_id, create_tile_layer(l, options.tile_layer).addTo(map));
+        }
+        _tile_configs.set(map_id, tile_config);
+    }
 }
 
-export function delete_map(map_id: Id) {
-    _maps.delete(map_id);
+export async function update_map_options(map_id: Id, options: MapOptions, call: Call): Promise<void> {
+    return recording_errors(call, async () => {
+        const l = await setup();
+        apply_map_options(l, map_id, find_map(map_id), options);
+    });
 }
 
-export async function on_map_click(map_id: Id, callback: RustCallback<number[], void>): Promise<void> {
-    await setup();
-    const map = await get_map(map_id);
-    if (!map) {
-        throw new Error(`Map with id ${map_id} not found when setting onClick handler`);
-    }
+export async function update_map(map_id: Id, initial_position: MapPosition, options: MapOptions, call: Call): Promise<void> {
+    return recording_errors(call, async () => {
+        const l = await setup();
+
+        const existing = _maps.get(map_id);
+        if (existing) {
+            apply_map_options(l, map_id, existing, options);
+            return;
+        }
+
+        // Options that can change later are applied right after
+        const map = l.map(`dioxus-leaflet-map-${map_id}`, {
+            zoomControl: false,
+            attributionControl: false,
+            crs: map_crs(l, options.crs),
+            minZoom: options.min_zoom ?? undefined,
+            maxZoom: options.max_zoom ?? undefined,
+            maxBounds: options.max_bounds ? to_latlng_bounds(l, options.max_bounds) : undefined,
+            preferCanvas: options.prefer_canvas,
+            zoomAnimation: options.zoom_animation,
+            fadeAnimation: options.fade_animation,
+        });
+        map.setView(initial_position.coordinates, initial_position.zoom);
+        apply_map_options(l, map_id, map, options);
 
-    map.on("click", async (e: L.LeafletMouseEvent) => {
-        try {
-            await callback([e.latlng.lat, e.latlng.lng]);
-        } catch (error) {
-            console.error("Error in on_map_click callback:", error);
+        _maps.set(map_id, map);
+        register_container(map_id, map);
+
+        // Resolve any pending promises
+        if (_callbacks.has(map_id)) {
+            const callback = _callbacks.get(map_id)!;
+            callback(map);
+            _callbacks.delete(map_id);
+            _promises.delete(map_id);
         }
+
+        // Force resize to ensure proper display
+        await wait(100);
+        map.invalidateSize();
     });
 }
 
-export async function on_map_move(map_id: Id, callback: RustCallback<number[], void>): Promise<void> {
-    await setup();
-    const map = await get_map(map_id);
-    if (!map) {
-        throw new Error(`Map with id ${map_id} not found when setting onMove handler`);
-    }
+export async function delete_map(map_id: Id, call: Call) {
+    return recording_errors(call, async () => {
+        _maps.delete(map_id);
+        _tiles.delete(map_id);
+        _tile_configs.delete(map_id);
+        unregister_container(map_id);
+    });
+}
 
-    map.on("move", async () => {
-        const center = map.getCenter();
-        const zoom = map.getZoom();
-        try {
-            await callback([center.lat, center.lng, zoom]);
-        } catch (error) {
-            console.error("Error in map_on_move callback:", error);
+export async function on_map_click(map_id: Id, callback: RustCallback<number[], void>, call: Call): Promise<void> {
+    return recording_errors(call, async () => {
+        await setup();
+        const map = await get_map(map_id);
+        if (!map) {
+            throw new Error(`Map with id ${map_id} not found when setting onClick handler`);
+        }
+
+        map.on("click", async (e: L.LeafletMouseEvent) => {
+            try {
+                await callback([e.latlng.lat, e.latlng.lng]);
+            } catch (error) {
+                console.error("Error in on_map_click callback:", error);
+            }
+        });
+    });
+}
+
+export async function on_map_move(map_id: Id, callback: RustCallback<number[], void>, call: Call): Promise<void> {
+    return recording_errors(call, async () => {
+        await setup();
+        const map = await get_map(map_id);
+        if (!map) {
+            throw new Error(`Map with id ${map_id} not found when setting onMove handler`);
+        }
+
+        map.on("move", async () => {
+            const center = map.getCenter();
+            const zoom = map.getZoom();
+            try {
+                await callback([center.lat, center.lng, zoom]);
+            } catch (error) {
+                console.error("Error in map_on_move callback:", error);
+            }
+        });
+    });
+}
+
+export async function on_map_move_end(map_id: Id, callback: RustCallback<number[], void>, call: Call): Promise<void> {
+    return recording_errors(call, async () => {
+        await setup();
+        const map = await get_map(map_id);
+        if (!map) {
+            throw new Error(`Map with id ${map_id} not found when setting onMoveEnd handler`);
         }
+
+        map.on("moveend", async () => {
+            const center = map.getCenter();
+            const zoom = map.getZoom();
+            try {
+                await callback([center.lat, center.lng, zoom]);
+            } catch (error) {
+                console.error("Error in on_map_move_end callback:", error);
+            }
+        });
+    });
+}
+
+export async function set_map_view(map_id: Id, position: MapPosition, call: Call): Promise<void> {
+    return recording_errors(call, async () => {
+        const map = find_map(map_id);
+        map.setView(position.coordinates, position.zoom);
+    });
+}
+
+export async function fly_map_to(map_id: Id, position: MapPosition, call: Call): Promise<void> {
+    return recording_errors(call, async () => {
+        const map = find_map(map_id);
+        map.flyTo(position.coordinates, position.zoom);
     });
-}
\ No newline at end of file
+}
+
+export async function pan_map_to(map_id: Id, coordinates: L.LatLngExpression, call: Call): Promise<void> {
+    return recording_errors(call, async () => {
+        const map = find_map(map_id);
+        map.panTo(coordinates);
+    });
+}
+
+export async function set_map_zoom(map_id: Id, zoom: number, call: Call): Promise<void> {
+    return recording_errors(call, async () => {
+        const map = find_map(map_id);
+        map.setZoom(zoom);
+    });
+}
+
+export async function zoom_map_in(map_id: Id, call: Call): Promise<void> {
+    return recording_errors(call, async () => {
+        const map = find_map(map_id);
+        map.zoomIn();
+    });
+}
+
+export async function zoom_map_out(map_id: Id, call: Call): Promise<void> {
+    return recording_errors(call, async () => {
+        const map = find_map(map_id);
+        map.zoomOut();
+    });
+}
+
+export async function fit_map_bounds(map_id: Id, bounds: LatLngBounds, call: Call): Promise<void> {
+    return recording_errors(call, async () => {
+        const l = await setup();
+        const map = find_map(map_id);
+        map.fitBounds(to_latlng_bounds(l, bounds));
+    });
+}
+
+export async function invalidate_map_size(map_id: Id, call: Call): Promise<void> {
+    return recording_errors(call, async () => {
+        const map = find_map(map_id);
+        map.invalidateSize();
+    });
+}
+
+export async function get_map_center(map_id: Id, call: Call): Promise<number[]> {
+    return recording_errors(call, async () => {
+        const map = find_map(map_id);
+        const center = map.getCenter();
+        return [center.lat, center.lng];
+    });
+}
+
+export async function get_map_zoom(map_id: Id, call: Call): Promise<number> {
+    return recording_errors(call, async () => {
+        const map = find_map(map_id);
+        return map.getZoom();
+    });
+}
+
+export async function get_map_bounds(map_id: Id, call: Call): Promise<number[]> {
+    return recording_errors(call, async () => {
+        const map = find_map(map_id);
+        const bounds = map.getBounds();
+        return [bounds.getSouth(), bounds.getWest(), bounds.getNorth(), bounds.getEast()];
+    });
+}
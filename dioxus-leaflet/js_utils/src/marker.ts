@@ -1,48 +1,99 @@
-import type { L, Id } from "./types";
-import { get_map } from "./map";
-import { setup } from "./util";
-import { get_popup } from "./popup";
+import type { L, Id, Call } from "./types";
+import { setup, recording_errors } from "./util";
+import { add_to_container, register_layer, remove_from_container, unregister_layer, wait_for_container, wait_for_layer } from "./layer";
 
 const _markers = new Map<Id, L.Marker>();
+const _marker_data = new WeakMap<L.Layer, Record<string, string>>();
+const _marker_icons = new Map<Id, L.IconOptions>();
+const _marker_contents = new Map<Id, HTMLElement>();
 
 export function get_marker(marker_id: Id): L.Marker | undefined {
     return _markers.get(marker_id);
 }
 
-export async function update_marker(map_id: Id, marker_id: Id, coordinate: L.LatLngExpression, icon?: L.IconOptions) {
-    const l = await setup();
-    const map = await get_map(map_id);
-    if (!map) {
-        throw new Error(`Map with id ${map_id} not found when updating marker ${marker_id}`);
-    }
-
-    const marker = _markers.get(marker_id) ?? l.marker([0, 0]).addTo(map);
-    _markers.set(marker_id, marker);
-
-    marker.setLatLng(coordinate);
-    if (icon) {
-        marker.setIcon(l.icon(icon));
-    }
-
-    const popup = get_popup(marker_id);
-    if (popup) {
-        marker.unbindPopup();
-        marker.bindPopup(popup.body, popup.options);
-    }
+export function get_marker_custom_data(marker: L.Layer): Record<string, string> | undefined {
+    return _marker_data.get(marker);
 }
 
-export async function delete_marker(map_id: Id, marker_id: Id) {
-    const l = await setup();
-    const map = await get_map(map_id);
-    if (!map) {
-        throw new Error(`Map with id ${map_id} not found when deleting marker ${marker_id}`);
-    }
-
-    const marker = _markers.get(marker_id);
-    if (!marker) {
-        throw new Error(`Marker with id ${marker_id} not found when deleting`);
-    }
-
-    map.removeLayer(marker);
-    _markers.delete(marker_id);
-}
\ No newline at end of file
+export async function update_marker(parent_id: Id, marker_id: Id, coordinate: L.LatLngExpression, icon: L.IconOptions | null, draggable: boolean, custom_data: Record<string, string> | null, call: Call) {
+    return recording_errors(call, async () => {
+        const l = await setup();
+        const container = await wait_for_container(parent_id);
+
+        let marker = _markers.get(marker_id);
+        if (!marker) {
+            marker = l.marker(coordinate);
+            add_to_container(parent_id, container, marker);
+            _markers.set(marker_id, marker);
+            register_layer(marker_id, marker);
+        }
+
+        marker.setLatLng(coordinate);
+        // Content rendered from RSX replaces the icon
+        if (icon) {
+            _marker_icons.set(marker_id, icon);
+            if (!_marker_contents.has(marker_id)) {
+                marker.setIcon(l.icon(icon));
+            }
+        } else if (_marker_icons.delete(marker_id) && !_marker_contents.has(marker_id)) {
+            marker.setIcon(new l.Icon.Default());
+        }
+
+        if (custom_data) {
+            _marker_data.set(marker, custom_data);
+        } else {
+            _marker_data.delete(marker);
+        }
+
+        // Markers waiting to be added to a cluster group have no drag handler yet, it reads the option once added
+        marker.options.draggable = draggable;
+        if (draggable) {
+            marker.dragging?.enable();
+        } else {
+            marker.dragging?.disable();
+        }
+    });
+}
+
+export async function delete_marker(parent_id: Id, marker_id: Id, call: Call) {
+    return recording_errors(call, async () => {
+        const l = await setup();
+
+        const marker = _markers.get(marker_id);
+        if (!marker) {
+            throw new Error(`Marker with id ${marker_id} not found when deleting`);
+        }
+
+        remove_from_container(parent_id, marker);
+        marker.remove();
+        _markers.delete(marker_id);
+        _marker_icons.delete(marker_id);
+        unregister_layer(marker_id);
+    });
+}
+export async function update_marker_content(marker_id: Id, options: L.DivIconOptions, call: Call) {
+    return recording_errors(call, async () => {
+        const l = await setup();
+
+        // Leaflet moves the content into the icon element, so keep a reference to it
+        const id = `dioxus-leaflet-marker-${marker_id}-content`;
+        const content = _marker_contents.get(marker_id) ?? document.getElementById(id);
+        if (!content) {
+            throw new Error(`Marker content element with id ${id} not found when updating marker ${marker_id}`);
+        }
+        _marker_contents.set(marker_id, content);
+
+        const marker = await wait_for_layer(marker_id) as L.Marker;
+        marker.setIcon(l.divIcon({ ...options, html: content }));
+    });
+}
+
+export async function delete_marker_content(marker_id: Id, call: Call) {
+    return recording_errors(call, async () => {
+        const l = await setup();
+
+        _marker_contents.delete(marker_id);
+        const icon = _marker_icons.get(marker_id);
+        _markers.get(marker_id)?.setIcon(icon ? l.icon(icon) : new l.Icon.Default());
+    });
+}
@@ -0,0 +1,167 @@
+import type { L, Id, Json, RustCallback, Call } from "./types";
+import { setup, recording_errors } from "./util";
+import { get_container, register_container, register_layer, unregister_container, unregister_layer, wait_for_container } from "./layer";
+import { get_marker_custom_data } from "./marker";
+
+// Leaflet.markercluster ships no typings in this package
+interface MarkerCluster extends L.Marker {
+    getChildCount(): number;
+    getAllChildMarkers(): L.Marker[];
+}
+
+interface MarkerClusterGroup extends L.FeatureGroup {
+    refreshClusters(): this;
+    addLayers(layers: L.Layer[]): this;
+    _defaultIconCreateFunction(cluster: MarkerCluster): L.Icon | L.DivIcon;
+}
+
+const _cluster_groups = new Map<Id, MarkerClusterGroup>();
+const _cluster_group_options = new Map<Id, string>();
+const _cluster_icons = new Map<Id, Map<number, L.DivIcon>>();
+const _cluster_icon_requests = new Map<Id, Set<number>>();
+const _cluster_icon_callbacks = new Map<Id, RustCallback<number, Json>>();
+const _cluster_clicks = new Map<Id, RustCallback<Json, void>>();
+const _cluster_scripts = new Map<string, Promise<void>>();
+
+async function load_marker_cluster(l: typeof L, script_url: string, script_integrity: string | null): Promise<void> {
+    if ((l as any).markerClusterGroup) {
+        return;
+    }
+
+    // The plugin needs Leaflet to be loaded first, so it is only added once Leaflet is available
+    let loading = _cluster_scripts.get(script_url);
+    if (!loading) {
+        loading = new Promise<void>((resolve, reject) => {
+            const script = document.createElement("script");
+            script.src = script_url;
+            if (script_integrity) {
+                script.integrity = script_integrity;
+                script.crossOrigin = "anonymous";
+            }
+            script.onload = () => resolve();
+            script.onerror = () => {
+                _cluster_scripts.delete(script_url);
+                reject(new Error(`Failed to load Leaflet.markercluster from ${script_url}`));
+            };
+            document.head.appendChild(script);
+        });
+        _cluster_scripts.set(script_url, loading);
+    }
+    await loading;
+}
+
+function create_cluster_group(l: typeof L, group_id: Id, options: object): MarkerClusterGroup {
+    const group: MarkerClusterGroup = (l as any).markerClusterGroup({
+        ...options,
+        iconCreateFunction: (cluster: MarkerCluster) => {
+            const count = cluster.getChildCount();
+            const icon = _cluster_icons.get(group_id)?.get(count);
+            if (icon) {
+                return icon;
+            }
+            request_cluster_icon(l, group_id, count);
+            return group._defaultIconCreateFunction(cluster);
+        },
+    });
+
+    group.on("clusterclick", async (e: L.LeafletEvent) => {
+        const callback = _cluster_clicks.get(group_id);
+        if (!callback) {
+            return;
+        }
+        const cluster = (e as L.LeafletEvent & { layer: MarkerCluster }).layer;
+        const data = cluster
+            .getAllChildMarkers()
+            .map((marker) => get_marker_custom_data(marker))
+            .filter((data) => data !== undefined);
+        try {
+            await callback(data);
+        } catch (error) {
+            console.error("Error in cluster click callback:", error);
+        }
+    });
+
+    return group;
+}
+
+async function request_cluster_icon(l: typeof L, group_id: Id, count: number) {
+    const callback = _cluster_icon_callbacks.get(group_id);
+    const requests = _cluster_icon_requests.get(group_id) ?? new Set();
+    if (!callback || requests.has(count)) {
+        return;
+    }
+    requests.add(count);
+    _cluster_icon_requests.set(group_id, requests);
+
+    try {
+        const options = await callback(count) as L.DivIconOptions;
+        const icons = _cluster_icons.get(group_id) ?? new Map();
+        icons.set(count, l.divIcon(options));
+        _cluster_icons.set(group_id, icons);
+        _cluster_groups.get(group_id)?.refreshClusters();
+    } catch (error) {
+        console.error("Error in cluster icon callback:", error);
+    }
+}
+
+export async function update_marker_cluster_group(parent_id: Id, group_id: Id, script_url: string, script_integrity: string | null, options: Json, call: Call) {
+    return recording_errors(call, async () => {
+        const l = await setup();
+        await load_marker_cluster(l, script_url, script_integrity);
+        const container = await wait_for_container(parent_id);
+
+        // Clustering options are only read when the group is created, so changing them rebuilds it
+        const key = JSON.stringify(options);
+        const previous = _cluster_groups.get(group_id);
+        if (previous && _cluster_group_options.get(group_id) === key) {
+            return;
+        }
+
+        const group = create_cluster_group(l, group_id, options as object);
+        if (previous) {
+            const layers = previous.getLayers();
+            container.removeLayer(previous);
+            group.addLayers(layers);
+        }
+        container.addLayer(group);
+
+        _cluster_groups.set(group_id, group);
+        _cluster_group_options.set(group_id, key);
+        register_layer(group_id, group);
+        register_container(group_id, group);
+    });
+}
+
+export async function on_cluster_icon(group_id: Id, callback: RustCallback<number, Json>, call: Call): Promise<void> {
+    return recording_errors(call, async () => {
+        _cluster_icon_callbacks.set(group_id, callback);
+        _cluster_icons.delete(group_id);
+        _cluster_icon_requests.delete(group_id);
+        _cluster_groups.get(group_id)?.refreshClusters();
+    });
+}
+
+export async function on_cluster_click(group_id: Id, callback: RustCallback<Json, void>, call: Call): Promise<void> {
+    return recording_errors(call, async () => {
+        _cluster_clicks.set(group_id, callback);
+    });
+}
+
+export async function delete_marker_cluster_group(parent_id: Id, group_id: Id, call: Call) {
+    return recording_errors(call, async () => {
+        _cluster_icon_callbacks.delete(group_id);
+        _cluster_clicks.delete(group_id);
+        _cluster_icons.delete(group_id);
+        _cluster_icon_requests.delete(group_id);
+
+        const group = _cluster_groups.get(group_id);
+        if (group) {
+            get_container(parent_id)?.removeLayer(group);
+            group.remove();
+            _cluster_groups.delete(group_id);
+            _cluster_group_options.delete(group_id);
+            unregister_layer(group_id);
+            unregister_container(group_id);
+        }
+    });
+}
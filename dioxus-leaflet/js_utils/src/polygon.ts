@@ -1,7 +1,6 @@
-import { setup } from "./util";
-import { get_map } from "./map";
-import type { L, Id, Json } from "./types";
-import { get_popup } from "./popup";
+import { setup, recording_errors } from "./util";
+import type { L, Id, Json, Call } from "./types";
+import { get_container, register_layer, unregister_layer, wait_for_container } from "./layer";
 
 const _gons = new Map<Id, L.Polygon>();
 
@@ -9,32 +8,33 @@ export function get_polygon(polygon_id: Id): L.Polygon | undefined {
     return _gons.get(polygon_id);
 }
 
-export async function update_polygon(map_id: Id, polygon_id: Id, coordinates: L.LatLngLiteral[][][], options: L.PathOptions) {
-    const l = await setup();
-    const map = await get_map(map_id);
-    if (!map) {
-        throw new Error(`Map with id ${map_id} not found when updating polygon ${polygon_id}`);
-    }
-
-    const gon = _gons.get(polygon_id) ?? l.polygon([]).addTo(map);
-    _gons.set(polygon_id, gon);
-
-    gon.setLatLngs(coordinates);
-    gon.setStyle(options);
-
-    const popup = get_popup(polygon_id);
-    if (popup) {
-        gon.unbindPopup();
-        gon.bindPopup(popup.body, popup.options);
-    }
+export async function update_polygon(parent_id: Id, polygon_id: Id, coordinates: L.LatLngLiteral[][][], options: L.PathOptions, call: Call) {
+    return recording_errors(call, async () => {
+        const l = await setup();
+        const container = await wait_for_container(parent_id);
+
+        let gon = _gons.get(polygon_id);
+        if (!gon) {
+            gon = l.polygon([]).addTo(container);
+            _gons.set(polygon_id, gon);
+            register_layer(polygon_id, gon);
+        }
+
+        gon.setLatLngs(coordinates);
+        gon.setStyle(options);
+    });
 }
 
-export async function delete_polygon(map_id: Id, polygon_id: Id) {
-    const l = await setup();
-    const map = await get_map(map_id);
-    if (!map) {
-        throw new Error(`Map with id ${map_id} not found when deleting polygon ${polygon_id}`);
-    }
-
-    _gons.delete(polygon_id);
+export async function delete_polygon(parent_id: Id, polygon_id: Id, call: Call) {
+    return recording_errors(call, async () => {
+        const l = await setup();
+
+        const gon = _gons.get(polygon_id);
+        if (gon) {
+            get_container(parent_id)?.removeLayer(gon);
+            gon.remove();
+            _gons.delete(polygon_id);
+            unregister_layer(polygon_id);
+        }
+    });
 }
\ No newline at end of file
@@ -1,7 +1,6 @@
-import { setup } from "./util";
-import { get_map } from "./map";
-import type { L, Id } from "./types";
-import { get_popup } from "./popup";
+import { setup, recording_errors } from "./util";
+import type { L, Id, Call } from "./types";
+import { get_container, register_layer, unregister_layer, wait_for_container } from "./layer";
 
 const _lines = new Map<Id, L.Polyline>();
 
@@ -9,36 +8,33 @@ export function get_polyline(polyline_id: Id): L.Polyline | undefined {
     return _lines.get(polyline_id);
 }
 
-export async function update_polyline(map_id: Id, polyline_id: Id, coordinates: L.LatLngLiteral[][], options: L.PathOptions) {
-    const l = await setup();
-    const map = await get_map(map_id);
-    if (!map) {
-        throw new Error(`Map with id ${map_id} not found when updating polyline ${polyline_id}`);
-    }
-
-    const line = _lines.get(polyline_id) ?? l.polyline([]).addTo(map);
-    _lines.set(polyline_id, line);
-
-    line.setLatLngs(coordinates);
-    line.setStyle(options);
-
-    const popup = get_popup(polyline_id);
-    if (popup) {
-        line.unbindPopup();
-        line.bindPopup(popup.body, popup.options);
-    }
+export async function update_polyline(parent_id: Id, polyline_id: Id, coordinates: L.LatLngLiteral[][], options: L.PathOptions, call: Call) {
+    return recording_errors(call, async () => {
+        const l = await setup();
+        const container = await wait_for_container(parent_id);
+
+        let line = _lines.get(polyline_id);
+        if (!line) {
+            line = l.polyline([]).addTo(container);
+            _lines.set(polyline_id, line);
+            register_layer(polyline_id, line);
+        }
+
+        line.setLatLngs(coordinates);
+        line.setStyle(options);
+    });
 }
 
-export async function delete_polyline(map_id: Id, polyline_id: Id) {
-    const l = await setup();
-    const map = await get_map(map_id);
-    if (!map) {
-        throw new Error(`Map with id ${map_id} not found when deleting polyline ${polyline_id}`);
-    }
-
-    const line = _lines.get(polyline_id);
-    if (line) {
-        line.remove();
-        _lines.delete(polyline_id);
-    }
+export async function delete_polyline(parent_id: Id, polyline_id: Id, call: Call) {
+    return recording_errors(call, async () => {
+        const l = await setup();
+
+        const line = _lines.get(polyline_id);
+        if (line) {
+            get_container(parent_id)?.removeLayer(line);
+            line.remove();
+            _lines.delete(polyline_id);
+            unregister_layer(polyline_id);
+        }
+    });
 }
@@ -1,31 +1,128 @@
-import { L, Id } from "./types";
-import { get_marker } from "./marker";
-import { get_polygon } from "./polygon";
-import { setup } from "./util";
+import type { L, Id, RustCallback, Call } from "./types";
+import { get_map } from "./map";
+import { get_layer, wait_for_layer } from "./layer";
+import { setup, recording_errors } from "./util";
 
-type PopupRecord = {
-    body: HTMLElement,
-    options: L.PopupOptions,
-};
+const _popup_bodies = new Map<Id, HTMLElement>();
+const _popups = new Map<Id, L.Popup>();
+const _popup_toggles = new Map<Id, RustCallback<boolean, void>>();
+// Popups currently opened on the map at a position instead of bound to a layer
+const _standalone_popups = new Set<Id>();
 
-const _popups = new Map<Id, PopupRecord>();
-
-export function get_popup(marker_id: Id): PopupRecord | undefined {
-    return _popups.get(marker_id);
+function get_popup_body(owner_id: Id, popup_id: Id): HTMLElement {
+    // Leaflet moves the body into its own container, so keep a reference to it
+    const id = `dioxus-leaflet-popup-${popup_id}`;
+    const body = _popup_bodies.get(popup_id) ?? document.getElementById(id);
+    if (!body) {
+        throw new Error(`Popup body element with id ${id} not found when updating popup for object ${owner_id}`);
+    }
+    _popup_bodies.set(popup_id, body);
+    return body;
 }
 
-export async function update_popup(marker_id: Id, popup_id: Id, options: L.PopupOptions) {
+async function get_or_create_popup(owner_id: Id, popup_id: Id, options: L.PopupOptions): Promise<L.Popup> {
     const l = await setup();
-    const id = `dioxus-leaflet-popup-${popup_id}`;
-    const body = document.getElementById(id);
-    if (!body) {
-        throw new Error(`Popup body element with id ${id} not found when updating popup for object ${marker_id}`);
+    const body = get_popup_body(owner_id, popup_id);
+
+    let popup = _popups.get(popup_id);
+    if (!popup) {
+        popup = l.popup(options).setContent(body);
+        popup.on("add", () => notify_popup_toggle(popup_id, true));
+        popup.on("remove", () => notify_popup_toggle(popup_id, false));
+        _popups.set(popup_id, popup);
+    } else {
+        l.Util.setOptions(popup, options);
+        if (popup.isOpen()) {
+            popup.update();
+        }
     }
-    _popups.set(marker_id, { body, options });
+    return popup;
+}
 
-    let context = get_marker(marker_id) ?? get_polygon(marker_id);
-    if (context) {
-        context.unbindPopup();
-        context.bindPopup(body, options);
+async function notify_popup_toggle(popup_id: Id, open: boolean) {
+    const callback = _popup_toggles.get(popup_id);
+    if (!callback) {
+        return;
+    }
+    try {
+        await callback(open);
+    } catch (error) {
+        console.error(`Error in popup ${open ? "open" : "close"} callback:`, error);
     }
-}
\ No newline at end of file
+}
+
+export async function update_popup(layer_id: Id, popup_id: Id, options: L.PopupOptions, open: boolean | null, call: Call) {
+    return recording_errors(call, async () => {
+        const popup = await get_or_create_popup(layer_id, popup_id, options);
+        if (_standalone_popups.delete(popup_id)) {
+            popup.close();
+        }
+
+        const layer = await wait_for_layer(layer_id);
+        if (layer.getPopup() !== popup) {
+            layer.bindPopup(popup);
+        }
+
+        if (open === true && !popup.isOpen()) {
+            layer.openPopup();
+        } else if (open === false && popup.isOpen()) {
+            layer.closePopup();
+        }
+    });
+}
+
+export async function update_standalone_popup(map_id: Id, popup_id: Id, position: L.LatLngExpression, options: L.PopupOptions, open: boolean | null, call: Call) {
+    return recording_errors(call, async () => {
+        const map = await get_map(map_id);
+        if (!map) {
+            throw new Error(`Map with id ${map_id} not found when updating popup ${popup_id}`);
+        }
+
+        const popup = await get_or_create_popup(map_id, popup_id, options);
+        popup.setLatLng(position);
+
+        // Without a controlled state, a standalone popup opens once placed and then stays closed once closed by the user
+        const placed = !_standalone_popups.has(popup_id);
+        _standalone_popups.add(popup_id);
+        if ((open === true || (open === null && placed)) && !popup.isOpen()) {
+            popup.openOn(map);
+        } else if (open === false && popup.isOpen()) {
+            popup.close();
+        }
+    });
+}
+
+export async function close_standalone_popup(popup_id: Id, call: Call) {
+    return recording_errors(call, async () => {
+        if (_standalone_popups.delete(popup_id)) {
+            _popups.get(popup_id)?.close();
+        }
+    });
+}
+
+export async function on_popup_toggle(popup_id: Id, callback: RustCallback<boolean, void>, call: Call): Promise<void> {
+    return recording_errors(call, async () => {
+        _popup_toggles.set(popup_id, callback);
+    });
+}
+
+export async function delete_popup(owner_id: Id, popup_id: Id, call: Call) {
+    return recording_errors(call, async () => {
+        // Closing below must not call back into the dropped component
+        _popup_toggles.delete(popup_id);
+        _popup_bodies.delete(popup_id);
+        _standalone_popups.delete(popup_id);
+
+        const popup = _popups.get(popup_id);
+        if (!popup) {
+            return;
+        }
+        _popups.delete(popup_id);
+
+        const layer = get_layer(owner_id);
+        if (layer?.getPopup() === popup) {
+            layer.unbindPopup();
+        }
+        popup.remove();
+    });
+}
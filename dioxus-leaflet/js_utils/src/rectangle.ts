@@ -0,0 +1,41 @@
+import { setup, to_latlng_bounds, recording_errors } from "./util";
+import type { L, Id, LatLngBounds, Call } from "./types";
+import { get_container, register_layer, unregister_layer, wait_for_container } from "./layer";
+
+const _rectangles = new Map<Id, L.Rectangle>();
+
+export function get_rectangle(rectangle_id: Id): L.Rectangle | undefined {
+    return _rectangles.get(rectangle_id);
+}
+
+export async function update_rectangle(parent_id: Id, rectangle_id: Id, bounds: LatLngBounds, options: L.PathOptions, call: Call) {
+    return recording_errors(call, async () => {
+        const l = await setup();
+        const container = await wait_for_container(parent_id);
+
+        const latlng_bounds = to_latlng_bounds(l, bounds);
+        let rect = _rectangles.get(rectangle_id);
+        if (!rect) {
+            rect = l.rectangle(latlng_bounds).addTo(container);
+            _rectangles.set(rectangle_id, rect);
+            register_layer(rectangle_id, rect);
+        }
+
+        rect.setBounds(latlng_bounds);
+        rect.setStyle(options);
+    });
+}
+
+export async function delete_rectangle(parent_id: Id, rectangle_id: Id, call: Call) {
+    return recording_errors(call, async () => {
+        const l = await setup();
+
+        const rect = _rectangles.get(rectangle_id);
+        if (rect) {
+            get_container(parent_id)?.removeLayer(rect);
+            rect.remove();
+            _rectangles.delete(rectangle_id);
+            unregister_layer(rectangle_id);
+        }
+    });
+}
@@ -0,0 +1,131 @@
+import type { L, Id, TileLayer, WmsOptions, Call } from "./types";
+import { setup, recording_errors } from "./util";
+import { get_container, register_layer, unregister_layer, wait_for_container } from "./layer";
+
+const _tile_layers = new Map<Id, L.TileLayer>();
+const _tile_layer_configs = new Map<Id, string>();
+const _wms_states = new Map<Id, { url: string, params: string, layer_options: string, extra_keys: string[] }>();
+
+export function create_tile_layer(l: typeof L, layer: TileLayer): L.TileLayer {
+    return l.tileLayer(layer.url, {
+        // Leaflet fills any other placeholder of the URL from the options
+        ...layer.url_variables,
+        attribution: layer.attribution,
+        minZoom: layer.min_zoom,
+        maxZoom: layer.max_zoom,
+        maxNativeZoom: layer.max_native_zoom ?? undefined,
+        subdomains: layer.subdomains,
+        tileSize: layer.tile_size,
+        zoomOffset: layer.zoom_offset,
+        tms: layer.tms,
+        opacity: layer.opacity,
+        zIndex: layer.z_index,
+        detectRetina: layer.detect_retina,
+        errorTileUrl: layer.error_tile_url ?? undefined,
+        bounds: layer.bounds ? l.latLngBounds(layer.bounds.south_west, layer.bounds.north_east) : undefined,
+        crossOrigin: layer.cross_origin ?? undefined,
+    });
+}
+
+export async function update_tile_layer(parent_id: Id, layer_id: Id, layer: TileLayer, opacity: number | null, z_index: number | null, call: Call) {
+    return recording_errors(call, async () => {
+        const l = await setup();
+        const container = await wait_for_container(parent_id);
+
+        // Opacity and z-index are updated in place, any other change replaces the layer
+        const config = JSON.stringify({ ...layer, opacity: null, z_index: null });
+        let tiles = _tile_layers.get(layer_id);
+        if (tiles && _tile_layer_configs.get(layer_id) !== config) {
+            container.removeLayer(tiles);
+            unregister_layer(layer_id);
+            tiles = undefined;
+        }
+
+        if (!tiles) {
+            tiles = create_tile_layer(l, layer).addTo(container);
+            _tile_layers.set(layer_id, tiles);
+            _tile_layer_configs.set(layer_id, config);
+            register_layer(layer_id, tiles);
+        }
+
+        tiles.setOpacity(opacity ?? layer.opacity);
+        tiles.setZIndex(z_index ?? layer.z_index);
+    });
+}
+
+function wms_params(options: WmsOptions): L.WMSParams {
+    return {
+        ...options.extra_params,
+        layers: options.layers,
+        styles: options.styles,
+        format: options.format,
+        transparent: options.transparent,
+        version: options.version,
+    };
+}
+
+function wms_crs(l: typeof L, crs: string | null): L.CRS | undefined {
+    if (crs === null) {
+        return undefined;
+    }
+    const found = (l.CRS as unknown as Record<string, L.CRS>)[crs.replace(":", "")];
+    if (!found) {
+        console.warn(`Unknown WMS CRS ${crs}, using the map's own`);
+    }
+    return found;
+}
+
+export async function update_wms_layer(parent_id: Id, layer_id: Id, url: string, options: WmsOptions, opacity: number, z_index: number, call: Call) {
+    return recording_errors(call, async () => {
+        const l = await setup();
+        const container = await wait_for_container(parent_id);
+
+        // Leaflet can only change the request params of a WMS layer, and only by merging them,
+        // so a new CRS or attribution, or a removed extra param, replaces the layer
+        const params = wms_params(options);
+        const layer_options = JSON.stringify([options.crs, options.attribution]);
+        const extra_keys = Object.keys(options.extra_params);
+        let tiles = _tile_layers.get(layer_id) as L.TileLayer.WMS | undefined;
+        const state = _wms_states.get(layer_id);
+        if (tiles && state && (state.layer_options !== layer_options || state.extra_keys.some((key) => !extra_keys.includes(key)))) {
+            container.removeLayer(tiles);
+            unregister_layer(layer_id);
+            tiles = undefined;
+        }
+
+        if (!tiles || !state) {
+            tiles = l.tileLayer.wms(url, {
+                ...params,
+                attribution: options.attribution,
+                crs: wms_crs(l, options.crs),
+            }).addTo(container);
+            _tile_layers.set(layer_id, tiles);
+            register_layer(layer_id, tiles);
+        } else {
+            if (state.url !== url) {
+                tiles.setUrl(url);
+            }
+            if (state.params !== JSON.stringify(params)) {
+                tiles.setParams(params);
+            }
+        }
+        _wms_states.set(layer_id, { url, params: JSON.stringify(params), layer_options, extra_keys });
+
+        tiles.setOpacity(opacity);
+        tiles.setZIndex(z_index);
+    });
+}
+
+export async function delete_tile_layer(parent_id: Id, layer_id: Id, call: Call) {
+    return recording_errors(call, async () => {
+        const tiles = _tile_layers.get(layer_id);
+        if (tiles) {
+            get_container(parent_id)?.removeLayer(tiles);
+            tiles.remove();
+            _tile_layers.delete(layer_id);
+            _tile_layer_configs.delete(layer_id);
+            _wms_states.delete(layer_id);
+            unregister_layer(layer_id);
+        }
+    });
+}
@@ -0,0 +1,28 @@
+import type { L, Id, Call } from "./types";
+import { recording_errors } from "./util";
+import { get_layer, wait_for_layer } from "./layer";
+
+const _tooltip_bodies = new Map<Id, HTMLElement>();
+
+export async function update_tooltip(layer_id: Id, tooltip_id: Id, options: L.TooltipOptions, call: Call) {
+    return recording_errors(call, async () => {
+        // Leaflet moves the body into its own container, so keep a reference to it
+        const id = `dioxus-leaflet-tooltip-${tooltip_id}`;
+        const body = _tooltip_bodies.get(tooltip_id) ?? document.getElementById(id);
+        if (!body) {
+            throw new Error(`Tooltip body element with id ${id} not found when updating tooltip for object ${layer_id}`);
+        }
+        _tooltip_bodies.set(tooltip_id, body);
+
+        const layer = await wait_for_layer(layer_id);
+        layer.unbindTooltip();
+        layer.bindTooltip(body, options);
+    });
+}
+
+export async function delete_tooltip(layer_id: Id, tooltip_id: Id, call: Call) {
+    return recording_errors(call, async () => {
+        _tooltip_bodies.delete(tooltip_id);
+        get_layer(layer_id)?.unbindTooltip();
+    });
+}
@@ -5,11 +5,22 @@ export type RustCallback<A, R> = (arg: A) => Promise<R>;
 
 export type Id = number;
 
+// Token passed by Rust with each call, under which the message of its exception is kept
+export type Call = number;
+
 export interface MapPosition {
     coordinates: [number, number],
     zoom: number,
 }
 
+export interface LatLngBounds {
+    south_west: L.LatLngLiteral,
+    north_east: L.LatLngLiteral,
+}
+
+export type Crs = "EPSG3857" | "EPSG4326" | "EPSG3395" | "Simple"
+    | { Custom: { a: number, b: number, c: number, d: number } };
+
 export interface MapOptions {
     zoom_control?: boolean,
     scroll_wheel_zoom?: L.Zoom,
@@ -18,12 +29,59 @@ export interface MapOptions {
     dragging?: boolean,
     keyboard?: boolean,
     attribution_control?: boolean,
-    tile_layer: TileLayer,
+    crs: Crs,
+    box_zoom: boolean,
+    min_zoom: number | null,
+    max_zoom: number | null,
+    max_bounds: LatLngBounds | null,
+    max_bounds_viscosity: number,
+    zoom_snap: number,
+    zoom_delta: number,
+    wheel_px_per_zoom_level: number,
+    inertia: boolean,
+    world_copy_jump: boolean,
+    prefer_canvas: boolean,
+    zoom_animation: boolean,
+    fade_animation: boolean,
+    tile_layer: TileLayer | null,
 }
 
 export interface TileLayer {
     url: string,
     attribution: string,
+    min_zoom: number,
     max_zoom: number,
+    max_native_zoom: number | null,
     subdomains: string[],
+    tile_size: number,
+    zoom_offset: number,
+    tms: boolean,
+    opacity: number,
+    z_index: number,
+    detect_retina: boolean,
+    error_tile_url: string | null,
+    bounds: LatLngBounds | null,
+    cross_origin: L.CrossOrigin | null,
+    url_variables: { [name: string]: string },
+}
+
+export interface WmsOptions {
+    layers: string,
+    styles: string,
+    format: string,
+    transparent: boolean,
+    version: string,
+    crs: string | null,
+    attribution: string,
+    extra_params: { [key: string]: string },
+}
+
+export type PointLayer =
+    | { type: "marker", icon: L.IconOptions | null }
+    | { type: "circle_marker", radius: number, options: L.PathOptions };
+
+export interface GeoJsonItem {
+    feature: GeoJSON.Feature,
+    style: L.PathOptions | null,
+    point: PointLayer | null,
 }
@@ -1,14 +1,109 @@
-import type { L } from "./types";
+import type { L, LatLngBounds, Call } from "./types";
+
+interface LeafletFiles {
+    css: string,
+    js: string,
+    css_integrity: string | null,
+    js_integrity: string | null,
+}
+
+// Message of the exception thrown by each call from Rust, which only learns that the call threw
+const _js_errors = new Map<Call, string>();
+
+// Set once Leaflet failed to load, so that nothing keeps waiting for it
+let _leaflet_unavailable = false;
+let _leaflet_fallback: Promise<void> | null = null;
 
 export async function setup(): Promise<typeof L> {
     let l = (window as any).L as typeof L;
     while (!l) {
+        if (_leaflet_unavailable) {
+            throw new Error("Leaflet is not available");
+        }
         await wait(100);
         l = (window as any).L as typeof L;
     }
     return l;
 }
 
-export function wait(ms: number): Promise<void> {
+export async function is_leaflet_loaded(): Promise<boolean> {
+    return !!(window as any).L;
+}
+
+async function wait_for_leaflet(timeout_ms: number): Promise<boolean> {
+    const deadline = Date.now() + timeout_ms;
+    while (!(window as any).L) {
+        if (Date.now() >= deadline) {
+            return false;
+        }
+        await wait(100);
+    }
+    return true;
+}
+
+async function load_leaflet_fallback(files: LeafletFiles): Promise<void> {
+    _leaflet_fallback ??= new Promise<void>((resolve) => {
+        const link = document.createElement("link");
+        link.rel = "stylesheet";
+        link.href = files.css;
+        if (files.css_integrity) {
+            link.integrity = files.css_integrity;
+            link.crossOrigin = "anonymous";
+        }
+        document.head.appendChild(link);
+
+        const script = document.createElement("script");
+        script.src = files.js;
+        if (files.js_integrity) {
+            script.integrity = files.js_integrity;
+            script.crossOrigin = "anonymous";
+        }
+        script.onload = () => resolve();
+        script.onerror = () => resolve();
+        document.head.appendChild(script);
+    });
+    await _leaflet_fallback;
+}
+
+export async function load_leaflet(timeout_ms: number, fallback: LeafletFiles | null, call: Call): Promise<boolean> {
+    return recording_errors(call, async () => {
+        if (await wait_for_leaflet(timeout_ms)) {
+            return true;
+        }
+
+        if (fallback) {
+            console.warn(`Leaflet did not load within ${timeout_ms} ms, trying ${fallback.js}`);
+            await load_leaflet_fallback(fallback);
+            if (await wait_for_leaflet(timeout_ms)) {
+                return true;
+            }
+        }
+
+        _leaflet_unavailable = true;
+        return false;
+    });
+}
+
+// Runs the body of a function called from Rust, keeping the message of its exception for `take_js_error`
+export async function recording_errors(call: Call, body: () => Promise<any>): Promise<any> {
+    try {
+        return await body();
+    } catch (e) {
+        _js_errors.set(call, e instanceof Error ? e.message : String(e));
+        throw e;
+    }
+}
+
+export async function take_js_error(call: Call): Promise<string | null> {
+    const message = _js_errors.get(call) ?? null;
+    _js_errors.delete(call);
+    return message;
+}
+
+export async function wait(ms: number): Promise<void> {
     return new Promise((resolve) => setTimeout(resolve, ms));
+}
+
+export function to_latlng_bounds(l: typeof L, bounds: LatLngBounds): L.LatLngBounds {
+    return l.latLngBounds(bounds.south_west, bounds.north_east);
 }
\ No newline at end of file
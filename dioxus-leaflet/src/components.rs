@@ -4,11 +4,50 @@ pub use map::Map;
 mod marker;
 pub use marker::Marker;
 
+mod marker_content;
+pub use marker_content::MarkerContent;
+
 mod polygon;
 pub use polygon::Polygon;
 
 mod polyline;
 pub use polyline::Polyline;
 
+mod circle;
+pub use circle::Circle;
+
+mod circle_marker;
+pub use circle_marker::CircleMarker;
+
+mod rectangle;
+pub use rectangle::Rectangle;
+
+mod image_overlay;
+pub use image_overlay::{ImageOverlay, SvgOverlay, VideoOverlay};
+
+mod geojson;
+pub use geojson::{FeatureFn, GeoJson};
+
+mod layer_group;
+pub use layer_group::LayerGroup;
+
+mod feature_group;
+pub use feature_group::{FeatureGroup, FeatureGroupHandle};
+
+mod marker_cluster_group;
+pub use marker_cluster_group::MarkerClusterGroup;
+
+mod tile_layer;
+pub use tile_layer::TileLayer;
+
+mod wms_layer;
+pub use wms_layer::WmsLayer;
+
+mod layers_control;
+pub use layers_control::{BaseLayer, LayersControl, Overlay};
+
 mod popup;
 pub use popup::Popup;
+
+mod tooltip;
+pub use tooltip::Tooltip;
@@ -0,0 +1,48 @@
+use std::rc::Rc;
+
+use dioxus::{
+    core::{spawn_forever, use_drop},
+    prelude::*,
+};
+use dioxus_logger::tracing::error;
+
+use crate::{LatLng, PathOptions, hooks::use_error_reporter, interop, types::Id};
+
+/// Circle with a radius given in pixels, independent of the zoom level
+#[component]
+pub fn CircleMarker(
+    center: ReadSignal<LatLng>,
+    radius: ReadSignal<f64>,
+    #[props(default = PathOptions::default())] options: ReadSignal<PathOptions>,
+    children: Element,
+) -> Element {
+    let id: Rc<Id> = use_context();
+    let errors = use_error_reporter();
+    let id =
+        use_context_provider(|| Rc::new(Id::circle_marker(&id, dioxus_core::current_scope_id().0)));
+
+    let id2 = id.clone();
+    use_effect(move || {
+        let id = id2.clone();
+        let center = center();
+        let radius = radius();
+        let opts = options();
+        spawn(async move {
+            if let Err(e) = interop::update_circle_marker(&id, &center, radius, &opts).await {
+                errors.report(e);
+            }
+        });
+    });
+
+    let id2 = id.clone();
+    use_drop(move || {
+        let id = id2.clone();
+        spawn_forever(async move {
+            if let Err(e) = interop::delete_circle_marker(&id).await {
+                error!("{e}");
+            }
+        });
+    });
+
+    rsx!({ children })
+}
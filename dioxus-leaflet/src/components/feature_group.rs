@@ -0,0 +1,96 @@
+use std::{cell::Cell, rc::Rc};
+
+use dioxus::{
+    core::{spawn_forever, use_drop},
+    prelude::*,
+};
+use dioxus_logger::tracing::error;
+
+use crate::{
+    LatLng, LatLngBounds, LeafletError,
+    hooks::{use_error_reporter, use_layer_events},
+    interop,
+    types::Id,
+};
+
+/// Handle to a mounted [`FeatureGroup`], obtained through its `on_ready` callback
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FeatureGroupHandle {
+    id: Rc<Id>,
+}
+
+impl FeatureGroupHandle {
+    /// Returns the bounds covering all layers of the group, or `None` if it is empty
+    pub async fn bounds(&self) -> Result<Option<LatLngBounds>, LeafletError> {
+        interop::get_layer_group_bounds(&self.id).await
+    }
+}
+
+/// Group of layers that are shown or hidden together, with shared bounds and events
+#[component]
+pub fn FeatureGroup(
+    /// Whether the group and all of its layers are shown on the map
+    #[props(default = true)]
+    visible: ReadSignal<bool>,
+
+    /// Callback when any layer of the group is clicked
+    on_click: Option<EventHandler<LatLng>>,
+
+    on_mouseover: Option<EventHandler<LatLng>>,
+
+    on_mouseout: Option<EventHandler<LatLng>>,
+
+    on_contextmenu: Option<EventHandler<LatLng>>,
+
+    /// Callback when the group has been created, providing a handle to query it
+    on_ready: Option<EventHandler<FeatureGroupHandle>>,
+
+    children: Element,
+) -> Element {
+    let id: Rc<Id> = use_context();
+    let errors = use_error_reporter();
+    let id =
+        use_context_provider(|| Rc::new(Id::feature_group(&id, dioxus_core::current_scope_id().0)));
+
+    let ready = use_hook(|| Rc::new(Cell::new(false)));
+
+    let id2 = id.clone();
+    use_effect(move || {
+        let id = id2.clone();
+        let ready = ready.clone();
+        let visible = visible();
+        spawn(async move {
+            if let Err(e) = interop::update_layer_group(&id, visible).await {
+                errors.report(e);
+                return;
+            }
+            if let Some(on_ready) = on_ready
+                && !ready.replace(true)
+            {
+                on_ready.call(FeatureGroupHandle { id });
+            }
+        });
+    });
+
+    use_layer_events(
+        &id,
+        [
+            ("click", on_click),
+            ("mouseover", on_mouseover),
+            ("mouseout", on_mouseout),
+            ("contextmenu", on_contextmenu),
+        ],
+    );
+
+    let id2 = id.clone();
+    use_drop(move || {
+        let id = id2.clone();
+        spawn_forever(async move {
+            if let Err(e) = interop::delete_layer_group(&id).await {
+                error!("{e}");
+            }
+        });
+    });
+
+    rsx!({ children })
+}
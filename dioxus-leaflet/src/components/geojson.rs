@@ -0,0 +1,135 @@
+use std::rc::Rc;
+
+use dioxus::{
+    core::{spawn_forever, use_drop},
+    prelude::*,
+};
+use dioxus_logger::tracing::error;
+use serde_json::{Map, Value};
+
+use crate::{
+    Feature, FeatureCollection, Geometry, PathOptions, PointLayer,
+    hooks::use_error_reporter,
+    interop::{self, GeoJsonItem},
+    types::Id,
+};
+
+/// Function called by a [`GeoJson`] layer with each of its features, borrowed instead of copied.
+/// Built from a closure taking a `&Feature`, and doing nothing by default.
+pub struct FeatureFn<T>(Option<Rc<FeatureFnInner<T>>>);
+
+type FeatureFnInner<T> = dyn Fn(&Feature) -> T;
+
+impl<T> FeatureFn<T> {
+    /// Calls the function, returning `None` when it is not set
+    pub fn call(&self, feature: &Feature) -> Option<T> {
+        self.0.as_ref().map(|f| f(feature))
+    }
+}
+
+impl<T> Default for FeatureFn<T> {
+    fn default() -> Self {
+        Self(None)
+    }
+}
+
+impl<T> Clone for FeatureFn<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T> PartialEq for FeatureFn<T> {
+    fn eq(&self, other: &Self) -> bool {
+        match (&self.0, &other.0) {
+            (Some(a), Some(b)) => Rc::ptr_eq(a, b),
+            (a, b) => a.is_none() && b.is_none(),
+        }
+    }
+}
+
+impl<T, F: Fn(&Feature) -> T + 'static> From<F> for FeatureFn<T> {
+    fn from(f: F) -> Self {
+        Self(Some(Rc::new(f)))
+    }
+}
+
+/// Layer displaying the features of a GeoJSON `FeatureCollection`
+#[component]
+pub fn GeoJson(
+    data: ReadSignal<FeatureCollection>,
+
+    /// Style applied to each line and polygon feature
+    #[props(default, into)]
+    style: FeatureFn<PathOptions>,
+
+    /// Layer used to display each point feature. Defaults to a marker.
+    #[props(default, into)]
+    point_to_layer: FeatureFn<PointLayer>,
+
+    /// Only features for which this returns `true` are displayed
+    #[props(default, into)]
+    filter: FeatureFn<bool>,
+
+    /// Callback when a feature is clicked, with the feature's properties
+    on_feature_click: Option<EventHandler<Map<String, Value>>>,
+
+    children: Element,
+) -> Element {
+    let id: Rc<Id> = use_context();
+    let errors = use_error_reporter();
+    let id = use_context_provider(|| Rc::new(Id::geojson(&id, dioxus_core::current_scope_id().0)));
+
+    // Passing new functions re-runs the effect, as well as the signals they read
+    let id2 = id.clone();
+    use_effect(use_reactive!(|(style, point_to_layer, filter)| {
+        let id = id2.clone();
+        let items: Vec<_> = data
+            .read()
+            .features
+            .iter()
+            .filter(|f| filter.call(f).unwrap_or(true))
+            .map(|feature| {
+                let is_point = matches!(feature.geometry, Some(Geometry::Point { .. }));
+                GeoJsonItem {
+                    feature: feature.clone(),
+                    style: style.call(feature),
+                    point: if is_point {
+                        point_to_layer.call(feature)
+                    } else {
+                        None
+                    },
+                }
+            })
+            .collect();
+        spawn(async move {
+            if let Err(e) = interop::update_geojson(&id, &items).await {
+                errors.report(e);
+            }
+        });
+    }));
+
+    let id2 = id.clone();
+    let _click_handle = use_resource(move || {
+        let id = id2.clone();
+        async move {
+            if let Some(on_feature_click) = on_feature_click
+                && let Err(e) = interop::on_geojson_feature_click(&id, on_feature_click).await
+            {
+                errors.report(e);
+            }
+        }
+    });
+
+    let id2 = id.clone();
+    use_drop(move || {
+        let id = id2.clone();
+        spawn_forever(async move {
+            if let Err(e) = interop::delete_geojson(&id).await {
+                error!("{e}");
+            }
+        });
+    });
+
+    rsx!({ children })
+}
@@ -0,0 +1,245 @@
+use dioxus::{
+    core::{spawn_forever, use_drop},
+    prelude::*,
+};
+use dioxus_logger::tracing::error;
+use std::rc::Rc;
+
+use crate::{
+    CrossOrigin, LatLng, LatLngBounds,
+    hooks::{use_error_reporter, use_layer_events},
+    interop::{self, OverlayOptions, VideoOptions},
+    types::Id,
+};
+
+/// Image stretched over the given bounds, like radar frames or scanned maps
+#[component]
+pub fn ImageOverlay(
+    /// URL of the image, an `Asset` can be passed as well
+    url: ReadSignal<String>,
+
+    bounds: ReadSignal<LatLngBounds>,
+
+    #[props(default = 1.0)] opacity: ReadSignal<f64>,
+
+    #[props(default = 1)] z_index: ReadSignal<i32>,
+
+    /// Text shown in place of the image for screen readers
+    #[props(into)]
+    alt: Option<String>,
+
+    /// CORS mode of the image request, needed to read the image back from a canvas
+    cross_origin: Option<CrossOrigin>,
+
+    /// Custom CSS class for the image element
+    #[props(into)]
+    class: Option<String>,
+
+    /// Whether the overlay receives mouse events, needed for child popups and tooltips.
+    /// Always the case when `on_click` is set.
+    #[props(default)]
+    interactive: bool,
+
+    on_click: Option<EventHandler<LatLng>>,
+
+    children: Element,
+) -> Element {
+    let id: Rc<Id> = use_context();
+    let errors = use_error_reporter();
+    let id =
+        use_context_provider(|| Rc::new(Id::image_overlay(&id, dioxus_core::current_scope_id().0)));
+
+    let id2 = id.clone();
+    use_effect(move || {
+        let id = id2.clone();
+        let url = url();
+        let bounds = bounds();
+        let opts = OverlayOptions {
+            opacity: opacity(),
+            z_index: z_index(),
+            interactive: interactive || on_click.is_some(),
+            class_name: class.clone(),
+            alt: alt.clone(),
+            cross_origin,
+        };
+        spawn(async move {
+            if let Err(e) = interop::update_image_overlay(&id, &url, &bounds, &opts).await {
+                errors.report(e);
+            }
+        });
+    });
+
+    use_layer_events(&id, [("click", on_click)]);
+    use_overlay_drop(&id);
+
+    rsx!({ children })
+}
+
+/// Video stretched over the given bounds, like animated radar imagery
+#[component]
+pub fn VideoOverlay(
+    /// URLs of the video in several formats, the first one the browser supports is played
+    urls: ReadSignal<Vec<String>>,
+
+    bounds: ReadSignal<LatLngBounds>,
+
+    #[props(default = 1.0)] opacity: ReadSignal<f64>,
+
+    #[props(default = 1)] z_index: ReadSignal<i32>,
+
+    #[props(default = true)] autoplay: ReadSignal<bool>,
+
+    #[props(default = true)] looped: ReadSignal<bool>,
+
+    /// Browsers only autoplay muted videos
+    #[props(default = true)]
+    muted: ReadSignal<bool>,
+
+    /// Whether the video keeps its aspect ratio instead of filling the bounds
+    #[props(default = true)]
+    keep_aspect_ratio: ReadSignal<bool>,
+
+    /// CORS mode of the video request
+    cross_origin: Option<CrossOrigin>,
+
+    /// Custom CSS class for the video element
+    #[props(into)]
+    class: Option<String>,
+
+    /// Whether the overlay receives mouse events, needed for child popups and tooltips.
+    /// Always the case when `on_click` is set.
+    #[props(default)]
+    interactive: bool,
+
+    on_click: Option<EventHandler<LatLng>>,
+
+    children: Element,
+) -> Element {
+    let id: Rc<Id> = use_context();
+    let errors = use_error_reporter();
+    let id =
+        use_context_provider(|| Rc::new(Id::image_overlay(&id, dioxus_core::current_scope_id().0)));
+
+    let id2 = id.clone();
+    use_effect(move || {
+        let id = id2.clone();
+        let urls = urls();
+        let bounds = bounds();
+        let opts = OverlayOptions {
+            opacity: opacity(),
+            z_index: z_index(),
+            interactive: interactive || on_click.is_some(),
+            class_name: class.clone(),
+            alt: None,
+            cross_origin,
+        };
+        let video = VideoOptions {
+            autoplay: autoplay(),
+            looped: looped(),
+            muted: muted(),
+            keep_aspect_ratio: keep_aspect_ratio(),
+        };
+        spawn(async move {
+            if let Err(e) = interop::update_video_overlay(&id, &urls, &bounds, &opts, &video).await
+            {
+                errors.report(e);
+            }
+        });
+    });
+
+    use_layer_events(&id, [("click", on_click)]);
+    use_overlay_drop(&id);
+
+    rsx!({ children })
+}
+
+/// SVG stretched over the given bounds, with its content rendered from RSX
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// # use dioxus_leaflet::{LatLng, LatLngBounds, SvgOverlay};
+/// # fn app() -> Element {
+/// rsx! {
+///     SvgOverlay {
+///         bounds: LatLngBounds::new(LatLng::new(0.0, 0.0), LatLng::new(100.0, 200.0)),
+///         view_box: "0 0 200 100",
+///         rect { x: 10, y: 10, width: 80, height: 40, fill: "steelblue" }
+///     }
+/// }
+/// # }
+/// ```
+#[component]
+pub fn SvgOverlay(
+    bounds: ReadSignal<LatLngBounds>,
+
+    /// `viewBox` of the SVG element, the coordinate system of its content
+    view_box: ReadSignal<String>,
+
+    #[props(default = 1.0)] opacity: ReadSignal<f64>,
+
+    #[props(default = 1)] z_index: ReadSignal<i32>,
+
+    /// Custom CSS class for the SVG element
+    #[props(into)]
+    class: Option<String>,
+
+    /// Whether the overlay receives mouse events, needed for child popups and tooltips.
+    /// Always the case when `on_click` is set.
+    #[props(default)]
+    interactive: bool,
+
+    on_click: Option<EventHandler<LatLng>>,
+
+    /// SVG elements drawn in the overlay
+    children: Element,
+) -> Element {
+    let id: Rc<Id> = use_context();
+    let errors = use_error_reporter();
+    let id =
+        use_context_provider(|| Rc::new(Id::image_overlay(&id, dioxus_core::current_scope_id().0)));
+
+    let id2 = id.clone();
+    use_effect(move || {
+        let id = id2.clone();
+        let bounds = bounds();
+        // The class is rendered on the element directly
+        let opts = OverlayOptions {
+            opacity: opacity(),
+            z_index: z_index(),
+            interactive: interactive || on_click.is_some(),
+            class_name: None,
+            alt: None,
+            cross_origin: None,
+        };
+        spawn(async move {
+            if let Err(e) = interop::update_svg_overlay(&id, &bounds, &opts).await {
+                errors.report(e);
+            }
+        });
+    });
+
+    use_layer_events(&id, [("click", on_click)]);
+    use_overlay_drop(&id);
+
+    rsx!(
+        svg {
+            id: "dioxus-leaflet-{id}",
+            class: "dioxus-leaflet-svg-overlay {class.as_deref().unwrap_or_default()}",
+            xmlns: "http://www.w3.org/2000/svg",
+            view_box: view_box(),
+            {children}
+        }
+    )
+}
+
+fn use_overlay_drop(id: &Rc<Id>) {
+    let id = id.clone();
+    use_drop(move || {
+        let id = id.clone();
+        spawn_forever(async move {
+            if let Err(e) = interop::delete_image_overlay(&id).await {
+                error!("{e}");
+            }
+        });
+    });
+}
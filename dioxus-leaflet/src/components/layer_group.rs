@@ -0,0 +1,47 @@
+use std::rc::Rc;
+
+use dioxus::{
+    core::{spawn_forever, use_drop},
+    prelude::*,
+};
+use dioxus_logger::tracing::error;
+
+use crate::{hooks::use_error_reporter, interop, types::Id};
+
+/// Group of layers that are shown or hidden together
+#[component]
+pub fn LayerGroup(
+    /// Whether the group and all of its layers are shown on the map
+    #[props(default = true)]
+    visible: ReadSignal<bool>,
+
+    children: Element,
+) -> Element {
+    let id: Rc<Id> = use_context();
+    let errors = use_error_reporter();
+    let id =
+        use_context_provider(|| Rc::new(Id::layer_group(&id, dioxus_core::current_scope_id().0)));
+
+    let id2 = id.clone();
+    use_effect(move || {
+        let id = id2.clone();
+        let visible = visible();
+        spawn(async move {
+            if let Err(e) = interop::update_layer_group(&id, visible).await {
+                errors.report(e);
+            }
+        });
+    });
+
+    let id2 = id.clone();
+    use_drop(move || {
+        let id = id2.clone();
+        spawn_forever(async move {
+            if let Err(e) = interop::delete_layer_group(&id).await {
+                error!("{e}");
+            }
+        });
+    });
+
+    rsx!({ children })
+}
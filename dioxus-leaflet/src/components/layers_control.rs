@@ -0,0 +1,147 @@
+use std::rc::Rc;
+
+use dioxus::{
+    core::{spawn_forever, use_drop},
+    prelude::*,
+};
+use dioxus_logger::tracing::error;
+
+use crate::{hooks::use_error_reporter, interop, types::Id};
+
+/// Control to switch between the `BaseLayer`s and toggle the `Overlay`s nested in it
+#[component]
+pub fn LayersControl(
+    /// Name of the shown base layer, kept in sync in both directions.
+    /// Defaults to the first base layer.
+    base: Option<Signal<String>>,
+
+    /// Names of the shown overlays, kept in sync in both directions.
+    /// All overlays are shown initially when not set.
+    overlays: Option<Signal<Vec<String>>>,
+
+    /// Whether the control is collapsed into an icon until hovered
+    #[props(default = true)]
+    collapsed: ReadSignal<bool>,
+
+    children: Element,
+) -> Element {
+    let id: Rc<Id> = use_context();
+    let errors = use_error_reporter();
+    let id = use_context_provider(|| {
+        Rc::new(Id::layers_control(&id, dioxus_core::current_scope_id().0))
+    });
+
+    let id2 = id.clone();
+    use_effect(move || {
+        let id = id2.clone();
+        let collapsed = collapsed();
+        spawn(async move {
+            if let Err(e) = interop::update_layers_control(&id, collapsed).await {
+                errors.report(e);
+            }
+        });
+    });
+
+    let id2 = id.clone();
+    use_effect(move || {
+        let id = id2.clone();
+        let base = base.map(|b| b());
+        let overlays = overlays.map(|o| o());
+        if base.is_none() && overlays.is_none() {
+            return;
+        }
+        spawn(async move {
+            if let Err(e) =
+                interop::set_layers_control_selection(&id, base.as_deref(), overlays.as_deref())
+                    .await
+            {
+                errors.report(e);
+            }
+        });
+    });
+
+    let id2 = id.clone();
+    let _change_handle = use_resource(move || {
+        let id = id2.clone();
+        async move {
+            if base.is_none() && overlays.is_none() {
+                return;
+            }
+            let on_change = EventHandler::new(move |selection: interop::LayersControlSelection| {
+                if let Some(mut base) = base
+                    && let Some(selected) = selection.base
+                    && *base.peek() != selected
+                {
+                    base.set(selected);
+                }
+                if let Some(mut overlays) = overlays
+                    && *overlays.peek() != selection.overlays
+                {
+                    overlays.set(selection.overlays);
+                }
+            });
+            if let Err(e) = interop::on_layers_control_change(&id, on_change).await {
+                errors.report(e);
+            }
+        }
+    });
+
+    let id2 = id.clone();
+    use_drop(move || {
+        let id = id2.clone();
+        spawn_forever(async move {
+            if let Err(e) = interop::delete_layers_control(&id).await {
+                error!("{e}");
+            }
+        });
+    });
+
+    rsx!({ children })
+}
+
+/// Base layer listed in its parent `LayersControl`, only one of them is shown at a time
+#[component]
+pub fn BaseLayer(name: ReadSignal<String>, children: Element) -> Element {
+    rsx! {
+        ControlLayer { name, overlay: false, {children} }
+    }
+}
+
+/// Overlay listed in its parent `LayersControl`, which can be shown or hidden
+#[component]
+pub fn Overlay(name: ReadSignal<String>, children: Element) -> Element {
+    rsx! {
+        ControlLayer { name, overlay: true, {children} }
+    }
+}
+
+#[component]
+fn ControlLayer(name: ReadSignal<String>, overlay: bool, children: Element) -> Element {
+    let id: Rc<Id> = use_context();
+    let errors = use_error_reporter();
+    let id =
+        use_context_provider(|| Rc::new(Id::control_layer(&id, dioxus_core::current_scope_id().0)));
+
+    let id2 = id.clone();
+    use_effect(move || {
+        let id = id2.clone();
+        let name = name();
+        spawn(async move {
+            if let Err(e) = interop::update_control_layer(&id, &name, overlay).await {
+                errors.report(e);
+            }
+        });
+    });
+
+    let id2 = id.clone();
+    use_drop(move || {
+        let id = id2.clone();
+        spawn_forever(async move {
+            if let Err(e) = interop::delete_control_layer(&id).await {
+                error!("{e}");
+            }
+        });
+    });
+
+    rsx!({ children })
+}
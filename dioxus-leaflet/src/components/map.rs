@@ -1,9 +1,12 @@
-use crate::{LatLng, MapOptions, MapPosition, interop, types::Id};
+use crate::{
+    LatLng, LeafletError, MapHandle, MapOptions, MapPosition, hooks::ErrorReporter, interop,
+    types::Id,
+};
 use dioxus::{
     core::{spawn_forever, use_drop},
     prelude::*,
 };
-use std::rc::Rc;
+use std::{cell::Cell, rc::Rc};
 
 const MAP_CSS: Asset = asset!("/assets/dioxus_leaflet.scss");
 
@@ -14,6 +17,11 @@ pub fn Map(
     #[props(default = MapPosition::default())]
     initial_position: MapPosition,
 
+    /// Position of the map, kept in sync in both directions: writing to the signal
+    /// moves the map, and panning or zooming the map writes back to the signal.
+    /// Takes precedence over `initial_position`.
+    position: Option<Signal<MapPosition>>,
+
     /// Height of the map container
     #[props(into)]
     height: Option<String>,
@@ -22,8 +30,10 @@ pub fn Map(
     #[props(into)]
     width: Option<String>,
 
-    /// Map configuration options
-    options: Option<MapOptions>,
+    /// Map configuration options, changes are applied to the existing map without
+    /// resetting its view
+    #[props(default)]
+    options: ReadSignal<MapOptions>,
 
     /// Custom CSS class for the map container
     #[props(into)]
@@ -39,26 +49,76 @@ pub fn Map(
     /// Callback when map is moved
     on_move: Option<EventHandler<MapPosition>>,
 
+    /// Callback when the map has been created, providing a handle to control it
+    on_ready: Option<EventHandler<MapHandle>>,
+
+    /// Callback when the map could not be created, for example when Leaflet failed to load
+    on_error: Option<EventHandler<LeafletError>>,
+
     children: Element,
 ) -> Element {
     let id = use_context_provider(|| Rc::new(Id::map(dioxus_core::current_scope_id().0)));
-    let options = options.unwrap_or(MapOptions::default());
-    let leaflet_css = options.leaflet_resources.css_url();
-    let leaflet_js = options.leaflet_resources.js_url();
+    let errors = use_context_provider(|| ErrorReporter::new(on_error));
+    let resources = options.read().leaflet_resources.clone();
+    let resources_error = resources.check_integrity().err();
     let height = height.unwrap_or_else(|| "400px".to_string());
     let width = width.unwrap_or_else(|| "100%".to_string());
     let style = style.unwrap_or_default();
     let class = class.unwrap_or_default();
 
+    let ready = use_hook(|| Rc::new(Cell::new(false)));
+    // Last position known to be shown by the map, used to break the position sync loop
+    let synced = use_hook(|| Rc::new(Cell::new(None::<MapPosition>)));
+
     let id2 = id.clone();
+    let ready2 = ready.clone();
+    let synced2 = synced.clone();
     let load_error = use_resource(move || {
         let id = id2.clone();
-        let pos = initial_position;
-        let opts = options.clone();
+        let ready = ready2.clone();
+        let synced = synced2.clone();
+        let pos = position.map(|p| *p.peek()).unwrap_or(initial_position);
+        synced.set(Some(pos));
+        let opts = options.peek().clone();
         async move {
-            interop::update_map(&id, &pos, &opts)
-                .await
-                .map_err(|e| e.to_string())
+            let result: Result<(), LeafletError> = async {
+                opts.leaflet_resources.check_integrity()?;
+                interop::load_leaflet(&opts).await?;
+                // Options may also have changed while Leaflet was loading
+                let mut applied = options.peek().clone();
+                interop::update_map(&id, &pos, &applied).await?;
+                // The effects skip changes until the map is ready, so catch up with the ones made
+                // in the meantime, until none is left when `ready` is set
+                loop {
+                    let latest = options.peek().clone();
+                    if latest != applied {
+                        interop::update_map_options(&id, &latest).await?;
+                        applied = latest;
+                        continue;
+                    }
+                    if let Some(position) = position {
+                        let latest = *position.peek();
+                        if synced.replace(Some(latest)) != Some(latest) {
+                            interop::set_map_view(&id, &latest).await?;
+                            continue;
+                        }
+                    }
+                    break;
+                }
+                Ok(())
+            }
+            .await;
+            match &result {
+                Ok(()) => {
+                    if !ready.replace(true)
+                        && let Some(on_ready) = on_ready
+                    {
+                        on_ready.call(MapHandle::new(&id));
+                    }
+                }
+                Err(e) => errors.report(e.clone()),
+            }
+            result
         }
     });
 
@@ -86,6 +146,58 @@ pub fn Map(
         }
     });
 
+    let id2 = id.clone();
+    let ready2 = ready.clone();
+    use_effect(move || {
+        let opts = options();
+        if !ready2.get() {
+            return;
+        }
+        let id = id2.clone();
+        spawn(async move {
+            if let Err(e) = interop::update_map_options(&id, &opts).await {
+                errors.report(e);
+            }
+        });
+    });
+
+    let id2 = id.clone();
+    let synced2 = synced.clone();
+    use_effect(move || {
+        let Some(position) = position else {
+            return;
+        };
+        let pos = position();
+        if !ready.get() || synced2.replace(Some(pos)) == Some(pos) {
+            return;
+        }
+        let id = id2.clone();
+        spawn(async move {
+            if let Err(e) = interop::set_map_view(&id, &pos).await {
+                errors.report(e);
+            }
+        });
+    });
+
+    let id2 = id.clone();
+    let _position_handle = use_resource(move || {
+        let id = id2.clone();
+        let synced = synced.clone();
+        async move {
+            if let Some(mut position) = position {
+                let on_move_end = EventHandler::new(move |pos: MapPosition| {
+                    synced.set(Some(pos));
+                    if *position.peek() != pos {
+                        position.set(pos);
+                    }
+                });
+                interop::on_map_move_end(&id, on_move_end).await
+            } else {
+                Ok(())
+            }
+        }
+    });
+
     let id2 = id.clone();
     use_drop(move || {
         let id = id2.clone();
@@ -95,13 +207,24 @@ pub fn Map(
     });
 
     rsx! {
-        // Leaflet CSS
-        document::Style { href: leaflet_css }
+        if resources_error.is_none() {
+            // Leaflet CSS
+            document::Link {
+                rel: "stylesheet",
+                href: resources.css_url(),
+                integrity: resources.css_integrity(),
+                crossorigin: resources.css_integrity().map(|_| "anonymous".to_string()),
+            }
 
-        document::Style { href: MAP_CSS }
+            // Leaflet JavaScript
+            document::Script {
+                src: resources.js_url(),
+                integrity: resources.js_integrity(),
+                crossorigin: resources.js_integrity().map(|_| "anonymous".to_string()),
+            }
+        }
 
-        // Leaflet JavaScript
-        document::Script { src: leaflet_js }
+        document::Style { href: MAP_CSS }
 
         // boot logic
         document::Script { src: interop::DL_JS, r#type: "module" }
@@ -5,7 +5,12 @@ use dioxus::{
 use dioxus_logger::tracing::error;
 use std::{collections::HashMap, rc::Rc};
 
-use crate::{LatLng, MarkerIcon, interop, types::Id};
+use crate::{
+    LatLng, MarkerIcon,
+    hooks::{use_error_reporter, use_layer_events},
+    interop,
+    types::Id,
+};
 
 #[component]
 pub fn Marker(
@@ -15,11 +20,34 @@ pub fn Marker(
 
     #[props(default = None)] custom_data: ReadSignal<Option<HashMap<String, String>>>,
 
-    on_click: Option<EventHandler>,
+    /// Whether the marker can be dragged with the mouse.
+    /// Update `coordinate` from `on_drag_end` to keep the new position.
+    #[props(default = false)]
+    draggable: ReadSignal<bool>,
+
+    on_click: Option<EventHandler<LatLng>>,
+
+    on_dblclick: Option<EventHandler<LatLng>>,
+
+    on_mouseover: Option<EventHandler<LatLng>>,
+
+    on_mouseout: Option<EventHandler<LatLng>>,
+
+    on_contextmenu: Option<EventHandler<LatLng>>,
+
+    /// Callback when the user starts dragging the marker
+    on_drag_start: Option<EventHandler<LatLng>>,
+
+    /// Callback while the marker is dragged, with its current position
+    on_drag: Option<EventHandler<LatLng>>,
+
+    /// Callback when the user drops the marker, with its new position
+    on_drag_end: Option<EventHandler<LatLng>>,
 
     children: Element,
 ) -> Element {
     let map: Rc<Id> = use_context();
+    let errors = use_error_reporter();
     let id = use_context_provider(|| Rc::new(Id::marker(&map, dioxus_core::current_scope_id().0)));
 
     let id2 = id.clone();
@@ -27,13 +55,31 @@ pub fn Marker(
         let id = id2.clone();
         let coord = coordinate();
         let icon = icon();
+        let draggable = draggable();
+        let custom_data = custom_data();
         spawn(async move {
-            if let Err(e) = interop::update_marker(&id, &coord, &icon).await {
-                error!("Error rendering marker: {e}");
+            if let Err(e) =
+                interop::update_marker(&id, &coord, &icon, draggable, &custom_data).await
+            {
+                errors.report(e);
             }
         });
     });
 
+    use_layer_events(
+        &id,
+        [
+            ("click", on_click),
+            ("dblclick", on_dblclick),
+            ("mouseover", on_mouseover),
+            ("mouseout", on_mouseout),
+            ("contextmenu", on_contextmenu),
+            ("dragstart", on_drag_start),
+            ("drag", on_drag),
+            ("dragend", on_drag_end),
+        ],
+    );
+
     let id2 = id.clone();
     use_drop(move || {
         let id = id2.clone();
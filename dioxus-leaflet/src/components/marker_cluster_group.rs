@@ -0,0 +1,132 @@
+use std::{collections::HashMap, rc::Rc};
+
+use dioxus::{
+    core::{spawn_forever, use_drop},
+    prelude::*,
+};
+use dioxus_logger::tracing::error;
+
+use crate::{
+    ClusterIcon, LatLng, MarkerClusterOptions, MarkerClusterResources,
+    hooks::{use_error_reporter, use_layer_events},
+    interop,
+    types::Id,
+};
+
+/// Group clustering its child markers, using the Leaflet.markercluster plugin
+#[component]
+pub fn MarkerClusterGroup(
+    #[props(default = MarkerClusterOptions::default())] options: ReadSignal<MarkerClusterOptions>,
+
+    /// Where to load Leaflet.markercluster from
+    #[props(default = MarkerClusterResources::default())]
+    resources: MarkerClusterResources,
+
+    /// Builds the icon of a cluster from the number of markers it contains.
+    /// Clusters use the plugin's default icons when not set.
+    cluster_icon: Option<Callback<usize, ClusterIcon>>,
+
+    /// Callback when a cluster is clicked, with the `custom_data` of its markers
+    on_cluster_click: Option<EventHandler<Vec<HashMap<String, String>>>>,
+
+    /// Callback when any marker of the group is clicked
+    on_click: Option<EventHandler<LatLng>>,
+
+    on_mouseover: Option<EventHandler<LatLng>>,
+
+    on_mouseout: Option<EventHandler<LatLng>>,
+
+    on_contextmenu: Option<EventHandler<LatLng>>,
+
+    children: Element,
+) -> Element {
+    let id: Rc<Id> = use_context();
+    let errors = use_error_reporter();
+    let id = use_context_provider(|| {
+        Rc::new(Id::marker_cluster_group(
+            &id,
+            dioxus_core::current_scope_id().0,
+        ))
+    });
+    let resources_error = resources.check_integrity().err();
+    let integrity = resources.integrity().cloned();
+    let css = resources.css_url();
+    let default_css = resources.default_css_url();
+    let js = resources.js_url();
+
+    let id2 = id.clone();
+    let js_integrity = integrity.as_ref().map(|integrity| integrity.js.clone());
+    let resources_error2 = resources_error.clone();
+    use_effect(move || {
+        let id = id2.clone();
+        let js = js.clone();
+        let js_integrity = js_integrity.clone();
+        let opts = options();
+        if let Some(e) = &resources_error2 {
+            errors.report(e.clone());
+            return;
+        }
+        spawn(async move {
+            if let Err(e) =
+                interop::update_marker_cluster_group(&id, &js, js_integrity.as_deref(), &opts).await
+            {
+                errors.report(e);
+            }
+        });
+    });
+
+    let id2 = id.clone();
+    let _events_handle = use_resource(move || {
+        let id = id2.clone();
+        async move {
+            if let Some(cluster_icon) = cluster_icon
+                && let Err(e) = interop::on_cluster_icon(&id, cluster_icon).await
+            {
+                errors.report(e);
+            }
+            if let Some(on_cluster_click) = on_cluster_click
+                && let Err(e) = interop::on_cluster_click(&id, on_cluster_click).await
+            {
+                errors.report(e);
+            }
+        }
+    });
+
+    use_layer_events(
+        &id,
+        [
+            ("click", on_click),
+            ("mouseover", on_mouseover),
+            ("mouseout", on_mouseout),
+            ("contextmenu", on_contextmenu),
+        ],
+    );
+
+    let id2 = id.clone();
+    use_drop(move || {
+        let id = id2.clone();
+        spawn_forever(async move {
+            if let Err(e) = interop::delete_marker_cluster_group(&id).await {
+                error!("{e}");
+            }
+        });
+    });
+
+    rsx! {
+        if resources_error.is_none() {
+            document::Link {
+                rel: "stylesheet",
+                href: css,
+                integrity: integrity.as_ref().map(|integrity| integrity.css.clone()),
+                crossorigin: integrity.as_ref().map(|_| "anonymous".to_string()),
+            }
+            document::Link {
+                rel: "stylesheet",
+                href: default_css,
+                integrity: integrity.as_ref().map(|integrity| integrity.default_css.clone()),
+                crossorigin: integrity.as_ref().map(|_| "anonymous".to_string()),
+            }
+        }
+        {children}
+    }
+}
@@ -0,0 +1,93 @@
+use dioxus::{
+    core::{spawn_forever, use_drop},
+    prelude::*,
+};
+use dioxus_logger::tracing::error;
+use std::rc::Rc;
+
+use crate::{
+    LeafletError,
+    hooks::use_error_reporter,
+    interop::{self, DivIconOptions},
+    types::Id,
+};
+
+/// Content of a marker rendered from RSX in place of its icon, like badges, status dots or SVG arrows.
+/// Must be placed directly in a `Marker`, and takes precedence over its `icon`.
+#[component]
+pub fn MarkerContent(
+    /// Size of the content in pixels, it is sized by its own content when not set
+    #[props(default)]
+    size: ReadSignal<Option<(u32, u32)>>,
+
+    /// Point of the content placed at the marker coordinate, in pixels from its top left corner.
+    /// Defaults to the center when `size` is set, and to the top left corner otherwise.
+    #[props(default)]
+    anchor: ReadSignal<Option<(i32, i32)>>,
+
+    /// Point popups open from, relative to `anchor`
+    #[props(default)]
+    popup_anchor: ReadSignal<Option<(i32, i32)>>,
+
+    /// Point tooltips open from, relative to `anchor`
+    #[props(default)]
+    tooltip_anchor: ReadSignal<Option<(i32, i32)>>,
+
+    /// Custom CSS class for the element Leaflet places on the map
+    #[props(into)]
+    class: Option<String>,
+
+    children: Element,
+) -> Element {
+    let id: Rc<Id> = use_context();
+    let errors = use_error_reporter();
+    let in_marker = matches!(*id, Id::Marker { .. });
+
+    let id2 = id.clone();
+    use_effect(move || {
+        let opts = DivIconOptions {
+            icon_size: size(),
+            icon_anchor: anchor(),
+            popup_anchor: popup_anchor(),
+            tooltip_anchor: tooltip_anchor(),
+            class_name: class.clone().unwrap_or_default(),
+        };
+        if !in_marker {
+            errors.report(LeafletError::Misplaced(format!(
+                "MarkerContent must be placed directly in a Marker, not in {id2}"
+            )));
+            return;
+        }
+        let id = id2.clone();
+        spawn(async move {
+            if let Err(e) = interop::update_marker_content(&id, &opts).await {
+                errors.report(e);
+            }
+        });
+    });
+
+    let id2 = id.clone();
+    use_drop(move || {
+        if !in_marker {
+            return;
+        }
+        let id = id2.clone();
+        spawn_forever(async move {
+            if let Err(e) = interop::delete_marker_content(&id).await {
+                error!("{e}");
+            }
+        });
+    });
+
+    if !in_marker {
+        return rsx!();
+    }
+
+    rsx!(
+        div {
+            id: "dioxus-leaflet-{id}-content",
+            class: "dioxus-leaflet-marker-content",
+            {children}
+        }
+    )
+}
@@ -5,7 +5,12 @@ use dioxus::{
 use dioxus_logger::tracing::error;
 use std::rc::Rc;
 
-use crate::{LatLng, PathOptions, interop, types::Id};
+use crate::{
+    LatLng, PathOptions,
+    hooks::{use_error_reporter, use_layer_events},
+    interop,
+    types::Id,
+};
 
 #[component]
 pub fn Polygon(
@@ -13,9 +18,18 @@ pub fn Polygon(
 
     #[props(default = PathOptions::default())] options: ReadSignal<PathOptions>,
 
+    on_click: Option<EventHandler<LatLng>>,
+
+    on_mouseover: Option<EventHandler<LatLng>>,
+
+    on_mouseout: Option<EventHandler<LatLng>>,
+
+    on_contextmenu: Option<EventHandler<LatLng>>,
+
     children: Element,
 ) -> Element {
     let id: Rc<Id> = use_context();
+    let errors = use_error_reporter();
     let id = use_context_provider(|| Rc::new(Id::polygon(&id, dioxus_core::current_scope_id().0)));
 
     let id2 = id.clone();
@@ -25,11 +39,21 @@ pub fn Polygon(
         let opts = options();
         spawn(async move {
             if let Err(e) = interop::update_polygon(&id, &coords, &opts).await {
-                error!("{e}");
+                errors.report(e);
             }
         });
     });
 
+    use_layer_events(
+        &id,
+        [
+            ("click", on_click),
+            ("mouseover", on_mouseover),
+            ("mouseout", on_mouseout),
+            ("contextmenu", on_contextmenu),
+        ],
+    );
+
     let id2 = id.clone();
     use_drop(move || {
         let id = id2.clone();
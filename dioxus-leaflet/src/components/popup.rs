@@ -1,24 +1,101 @@
-use crate::{PopupOptions, interop, types::Id};
-use dioxus::prelude::*;
+use crate::{LatLng, LeafletError, PopupOptions, hooks::use_error_reporter, interop, types::Id};
+use dioxus::{
+    core::{spawn_forever, use_drop},
+    prelude::*,
+};
 use dioxus_logger::tracing::error;
-use std::rc::Rc;
+use std::{cell::Cell, rc::Rc};
 
+/// Popup attached to its parent layer, or opened on the map at `position`
 #[component]
 pub fn Popup(
-    #[props(default = PopupOptions::default())] options: PopupOptions,
+    #[props(default = PopupOptions::default())] options: ReadSignal<PopupOptions>,
+
+    /// Opens the popup directly on the map at this position instead of binding it
+    /// to the parent layer. Required when the popup is a direct child of the map,
+    /// where setting it back to `None` closes the popup.
+    #[props(default)]
+    position: ReadSignal<Option<LatLng>>,
+
+    /// Whether the popup is open, kept in sync in both directions: writing to the
+    /// signal opens or closes the popup, and the user closing it writes back `false`.
+    open: Option<Signal<bool>>,
+
+    /// Callback when the popup opens
+    on_open: Option<EventHandler<()>>,
+
+    /// Callback when the popup closes
+    on_close: Option<EventHandler<()>>,
 
     children: Element,
 ) -> Element {
     let id: Rc<Id> = use_context();
+    let errors = use_error_reporter();
     let id = Id::popup(&id, dioxus_core::current_scope_id().0);
-    let class = options.class_name.clone();
+    let class = options.read().class_name.clone();
+
+    // Whether the popup was ever given a position, so that only a popup never placed is an error
+    let placed = use_hook(|| Rc::new(Cell::new(false)));
 
     let id2 = id.clone();
     use_effect(move || {
         let id = id2.clone();
-        let opts = options.clone();
+        let placed = placed.clone();
+        let opts = options();
+        let position = position();
+        let open = open.map(|o| o());
         spawn(async move {
-            if let Err(e) = interop::update_popup(&id, &opts).await {
+            let result = match position {
+                Some(position) => {
+                    placed.set(true);
+                    interop::update_standalone_popup(&id, &position, &opts, open).await
+                }
+                None if matches!(id.parent(), Some(Id::Map { .. })) => {
+                    if placed.get() {
+                        interop::close_standalone_popup(&id).await
+                    } else {
+                        Err(LeafletError::Misplaced(
+                            "Popup placed directly in a Map requires a position".to_string(),
+                        ))
+                    }
+                }
+                None => interop::update_popup(&id, &opts, open).await,
+            };
+            if let Err(e) = result {
+                errors.report(e);
+            }
+        });
+    });
+
+    let id2 = id.clone();
+    let _toggle_handle = use_resource(move || {
+        let id = id2.clone();
+        async move {
+            if open.is_none() && on_open.is_none() && on_close.is_none() {
+                return;
+            }
+            let on_toggle = EventHandler::new(move |is_open: bool| {
+                if let Some(mut open) = open
+                    && *open.peek() != is_open
+                {
+                    open.set(is_open);
+                }
+                let handler = if is_open { on_open } else { on_close };
+                if let Some(handler) = handler {
+                    handler.call(());
+                }
+            });
+            if let Err(e) = interop::on_popup_toggle(&id, on_toggle).await {
+                errors.report(e);
+            }
+        }
+    });
+
+    let id2 = id.clone();
+    use_drop(move || {
+        let id = id2.clone();
+        spawn_forever(async move {
+            if let Err(e) = interop::delete_popup(&id).await {
                 error!("{e}");
             }
         });
@@ -0,0 +1,46 @@
+use std::rc::Rc;
+
+use dioxus::{
+    core::{spawn_forever, use_drop},
+    prelude::*,
+};
+use dioxus_logger::tracing::error;
+
+use crate::{LatLngBounds, PathOptions, hooks::use_error_reporter, interop, types::Id};
+
+/// Axis-aligned rectangle covering the given bounds
+#[component]
+pub fn Rectangle(
+    bounds: ReadSignal<LatLngBounds>,
+    #[props(default = PathOptions::default())] options: ReadSignal<PathOptions>,
+    children: Element,
+) -> Element {
+    let id: Rc<Id> = use_context();
+    let errors = use_error_reporter();
+    let id =
+        use_context_provider(|| Rc::new(Id::rectangle(&id, dioxus_core::current_scope_id().0)));
+
+    let id2 = id.clone();
+    use_effect(move || {
+        let id = id2.clone();
+        let bounds = bounds();
+        let opts = options();
+        spawn(async move {
+            if let Err(e) = interop::update_rectangle(&id, &bounds, &opts).await {
+                errors.report(e);
+            }
+        });
+    });
+
+    let id2 = id.clone();
+    use_drop(move || {
+        let id = id2.clone();
+        spawn_forever(async move {
+            if let Err(e) = interop::delete_rectangle(&id).await {
+                error!("{e}");
+            }
+        });
+    });
+
+    rsx!({ children })
+}
@@ -0,0 +1,52 @@
+use std::rc::Rc;
+
+use dioxus::{
+    core::{spawn_forever, use_drop},
+    prelude::*,
+};
+use dioxus_logger::tracing::error;
+
+use crate::{TileLayerOptions, hooks::use_error_reporter, interop, types::Id};
+
+/// Tile layer added to its parent map or group
+#[component]
+pub fn TileLayer(
+    layer: ReadSignal<TileLayerOptions>,
+
+    /// Opacity of the tiles from 0 to 1, overriding the one of `layer`
+    #[props(default)]
+    opacity: ReadSignal<Option<f64>>,
+
+    /// Stacking order relative to the other tile layers, overriding the one of `layer`
+    #[props(default)]
+    z_index: ReadSignal<Option<i32>>,
+) -> Element {
+    let id: Rc<Id> = use_context();
+
+    let errors = use_error_reporter();
+    let id = Id::tile_layer(&id, dioxus_core::current_scope_id().0);
+
+    let id2 = id.clone();
+    use_effect(move || {
+        let id = id2.clone();
+        let layer = layer();
+        let opacity = opacity();
+        let z_index = z_index();
+        spawn(async move {
+            if let Err(e) = interop::update_tile_layer(&id, &layer, opacity, z_index).await {
+                errors.report(e);
+            }
+        });
+    });
+
+    use_drop(move || {
+        let id = id.clone();
+        spawn_forever(async move {
+            if let Err(e) = interop::delete_tile_layer(&id).await {
+                error!("{e}");
+            }
+        });
+    });
+
+    rsx!()
+}
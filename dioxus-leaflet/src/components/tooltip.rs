@@ -0,0 +1,65 @@
+use crate::{LeafletError, TooltipOptions, hooks::use_error_reporter, interop, types::Id};
+use dioxus::{
+    core::{spawn_forever, use_drop},
+    prelude::*,
+};
+use dioxus_logger::tracing::error;
+use std::rc::Rc;
+
+/// Tooltip attached to its parent layer, showing its children as content
+#[component]
+pub fn Tooltip(
+    #[props(default = TooltipOptions::default())] options: ReadSignal<TooltipOptions>,
+
+    children: Element,
+) -> Element {
+    let id: Rc<Id> = use_context();
+    let errors = use_error_reporter();
+    // Maps, groups and controls have no layer of their own to bind the tooltip to
+    let in_layer = !id.is_container() && !matches!(*id, Id::LayersControl { .. });
+    let id = Id::tooltip(&id, dioxus_core::current_scope_id().0);
+    let class = options.read().class_name.clone();
+
+    let id2 = id.clone();
+    use_effect(move || {
+        let opts = options();
+        if !in_layer {
+            errors.report(LeafletError::Misplaced(format!(
+                "Tooltip must be placed in a layer like a Marker, not in {}",
+                id2.parent().unwrap()
+            )));
+            return;
+        }
+        let id = id2.clone();
+        spawn(async move {
+            if let Err(e) = interop::update_tooltip(&id, &opts).await {
+                errors.report(e);
+            }
+        });
+    });
+
+    let id2 = id.clone();
+    use_drop(move || {
+        if !in_layer {
+            return;
+        }
+        let id = id2.clone();
+        spawn_forever(async move {
+            if let Err(e) = interop::delete_tooltip(&id).await {
+                error!("{e}");
+            }
+        });
+    });
+
+    if !in_layer {
+        return rsx!();
+    }
+
+    rsx!(
+        div {
+            id: "dioxus-leaflet-{id}",
+            class: "dioxus-leaflet-tooltip-content {class.as_deref().unwrap_or(\"\")}",
+            {children}
+        }
+    )
+}
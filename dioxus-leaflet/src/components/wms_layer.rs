@@ -0,0 +1,56 @@
+use std::rc::Rc;
+
+use dioxus::{
+    core::{spawn_forever, use_drop},
+    prelude::*,
+};
+use dioxus_logger::tracing::error;
+
+use crate::{WmsOptions, hooks::use_error_reporter, interop, types::Id};
+
+/// WMS tile layer added to its parent map or group.
+/// Changing only the request parameters updates the tiles in place.
+#[component]
+pub fn WmsLayer(
+    /// Base URL of the WMS service
+    url: ReadSignal<String>,
+
+    options: ReadSignal<WmsOptions>,
+
+    /// Opacity of the tiles, from 0 to 1
+    #[props(default = 1.0)]
+    opacity: ReadSignal<f64>,
+
+    /// Stacking order relative to the other tile layers
+    #[props(default = 1)]
+    z_index: ReadSignal<i32>,
+) -> Element {
+    let id: Rc<Id> = use_context();
+    let errors = use_error_reporter();
+    let id = Id::tile_layer(&id, dioxus_core::current_scope_id().0);
+
+    let id2 = id.clone();
+    use_effect(move || {
+        let id = id2.clone();
+        let url = url();
+        let options = options();
+        let opacity = opacity();
+        let z_index = z_index();
+        spawn(async move {
+            if let Err(e) = interop::update_wms_layer(&id, &url, &options, opacity, z_index).await {
+                errors.report(e);
+            }
+        });
+    });
+
+    use_drop(move || {
+        let id = id.clone();
+        spawn_forever(async move {
+            if let Err(e) = interop::delete_tile_layer(&id).await {
+                error!("{e}");
+            }
+        });
+    });
+
+    rsx!()
+}
@@ -0,0 +1,8 @@
+mod use_map;
+pub use use_map::{MapHandle, use_map};
+
+mod use_error_reporter;
+pub(crate) use use_error_reporter::{ErrorReporter, use_error_reporter};
+
+mod use_layer_events;
+pub(crate) use use_layer_events::use_layer_events;
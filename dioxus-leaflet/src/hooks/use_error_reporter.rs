@@ -0,0 +1,33 @@
+use dioxus::prelude::*;
+use dioxus_logger::tracing::error;
+
+use crate::LeafletError;
+
+/// Forwards the errors of the components of a map to its `on_error` callback.
+///
+/// Components only log the errors raised while they are removed, without reporting them,
+/// as the map may be removed along with them.
+#[derive(Clone, Copy)]
+pub(crate) struct ErrorReporter {
+    on_error: Option<EventHandler<LeafletError>>,
+}
+
+impl ErrorReporter {
+    pub(crate) fn new(on_error: Option<EventHandler<LeafletError>>) -> Self {
+        Self { on_error }
+    }
+
+    /// Logs the error and passes it to the map's `on_error` callback, if any
+    pub(crate) fn report(&self, e: LeafletError) {
+        error!("{e}");
+        if let Some(on_error) = self.on_error {
+            on_error.call(e);
+        }
+    }
+}
+
+/// Returns the error reporter of the closest enclosing [`Map`](crate::Map), which logs
+/// the errors it is given and passes them to the map's `on_error` callback
+pub(crate) fn use_error_reporter() -> ErrorReporter {
+    use_context()
+}
@@ -0,0 +1,27 @@
+use std::rc::Rc;
+
+use dioxus::prelude::*;
+
+use crate::{LatLng, hooks::use_error_reporter, interop, types::Id};
+
+/// Registers the handlers set for the given Leaflet events of a layer, called with the
+/// position of the event
+pub(crate) fn use_layer_events<const N: usize>(
+    id: &Rc<Id>,
+    events: [(&'static str, Option<EventHandler<LatLng>>); N],
+) {
+    let errors = use_error_reporter();
+    let id = id.clone();
+    let _events_handle = use_resource(move || {
+        let id = id.clone();
+        async move {
+            for (event, handler) in events {
+                if let Some(handler) = handler
+                    && let Err(e) = interop::on_layer_event(&id, event, handler).await
+                {
+                    errors.report(e);
+                }
+            }
+        }
+    });
+}
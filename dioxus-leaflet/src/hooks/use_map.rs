@@ -0,0 +1,88 @@
+use dioxus::prelude::*;
+use std::rc::Rc;
+
+use crate::{LatLng, LatLngBounds, LeafletError, MapPosition, interop, types::Id};
+
+/// Handle to a mounted [`Map`](crate::Map), used to control its view from Rust.
+///
+/// Obtained through [`use_map`] from any descendant of the map, or through the
+/// `on_ready` callback of the map itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MapHandle {
+    map_id: usize,
+}
+
+impl MapHandle {
+    pub(crate) fn new(map_id: &Id) -> Self {
+        Self {
+            map_id: map_id.map_id().id(),
+        }
+    }
+
+    fn id(&self) -> Id {
+        Id::map(self.map_id)
+    }
+
+    /// Sets the center and zoom of the map without animation
+    pub async fn set_view(&self, position: MapPosition) -> Result<(), LeafletError> {
+        interop::set_map_view(&self.id(), &position).await
+    }
+
+    /// Smoothly flies the map to the given center and zoom
+    pub async fn fly_to(&self, position: MapPosition) -> Result<(), LeafletError> {
+        interop::fly_map_to(&self.id(), &position).await
+    }
+
+    /// Pans the map to the given center, keeping the current zoom
+    pub async fn pan_to(&self, coordinates: LatLng) -> Result<(), LeafletError> {
+        interop::pan_map_to(&self.id(), &coordinates).await
+    }
+
+    /// Sets the zoom of the map, keeping the current center
+    pub async fn set_zoom(&self, zoom: f64) -> Result<(), LeafletError> {
+        interop::set_map_zoom(&self.id(), zoom).await
+    }
+
+    /// Zooms in by one step
+    pub async fn zoom_in(&self) -> Result<(), LeafletError> {
+        interop::zoom_map_in(&self.id()).await
+    }
+
+    /// Zooms out by one step
+    pub async fn zoom_out(&self) -> Result<(), LeafletError> {
+        interop::zoom_map_out(&self.id()).await
+    }
+
+    /// Sets the view so that the given bounds are visible at the highest possible zoom
+    pub async fn fit_bounds(&self, bounds: LatLngBounds) -> Result<(), LeafletError> {
+        interop::fit_map_bounds(&self.id(), &bounds).await
+    }
+
+    /// Recomputes the map size, to be called after its container was resized
+    pub async fn invalidate_size(&self) -> Result<(), LeafletError> {
+        interop::invalidate_map_size(&self.id()).await
+    }
+
+    /// Returns the current center of the map
+    pub async fn center(&self) -> Result<LatLng, LeafletError> {
+        interop::get_map_center(&self.id()).await
+    }
+
+    /// Returns the current zoom of the map
+    pub async fn zoom(&self) -> Result<f64, LeafletError> {
+        interop::get_map_zoom(&self.id()).await
+    }
+
+    /// Returns the currently visible area of the map
+    pub async fn bounds(&self) -> Result<LatLngBounds, LeafletError> {
+        interop::get_map_bounds(&self.id()).await
+    }
+}
+
+/// Returns a handle to the closest enclosing [`Map`](crate::Map).
+///
+/// Panics if called outside of a map.
+pub fn use_map() -> MapHandle {
+    let id: Rc<Id> = use_context();
+    use_hook(|| MapHandle::new(&id))
+}
@@ -1,8 +1,14 @@
+use dioxus::document::EvalError;
 use dioxus::prelude::*;
 use dioxus_use_js::{JsError, SerdeJsonValue};
-use std::error::Error;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
 
-use crate::{LatLng, MapOptions, MapPosition, MarkerIcon, PathOptions, PopupOptions, types::Id};
+use crate::{
+    ClusterIcon, CrossOrigin, Feature, LatLng, LatLngBounds, LeafletError, MapOptions, MapPosition,
+    MarkerClusterOptions, MarkerIcon, PathOptions, PointLayer, PopupOptions, TileLayerOptions,
+    TooltipOptions, WmsOptions, types::Id,
+};
 
 pub const DL_JS: Asset = asset!("/assets/dioxus_leaflet.js");
 
@@ -10,17 +16,158 @@ mod js_api {
     use dioxus::prelude::*;
     use dioxus_use_js::use_js;
 
-    use_js!("js_utils/src/map.ts", "assets/dioxus_leaflet.js"::{update_map, delete_map, on_map_click, on_map_move});
-    use_js!("js_utils/src/marker.ts", "assets/dioxus_leaflet.js"::{update_marker, delete_marker});
+    use_js!("js_utils/src/util.ts", "assets/dioxus_leaflet.js"::{load_leaflet, is_leaflet_loaded, take_js_error});
+    use_js!("js_utils/src/map.ts", "assets/dioxus_leaflet.js"::{
+        update_map, update_map_options, delete_map, map_exists, on_map_click, on_map_move, on_map_move_end,
+        set_map_view, fly_map_to, pan_map_to, set_map_zoom, zoom_map_in, zoom_map_out,
+        fit_map_bounds, invalidate_map_size, get_map_center, get_map_zoom, get_map_bounds
+    });
+    use_js!("js_utils/src/layer.ts", "assets/dioxus_leaflet.js"::{on_layer_event, layer_exists});
+    use_js!("js_utils/src/marker.ts", "assets/dioxus_leaflet.js"::{
+        update_marker, delete_marker, update_marker_content, delete_marker_content
+    });
     use_js!("js_utils/src/polygon.ts", "assets/dioxus_leaflet.js"::{update_polygon, delete_polygon});
     use_js!("js_utils/src/polyline.ts", "assets/dioxus_leaflet.js"::{update_polyline, delete_polyline});
-    use_js!("js_utils/src/popup.ts", "assets/dioxus_leaflet.js"::{update_popup});
+    use_js!("js_utils/src/circle.ts", "assets/dioxus_leaflet.js"::{update_circle, delete_circle});
+    use_js!("js_utils/src/circle_marker.ts", "assets/dioxus_leaflet.js"::{update_circle_marker, delete_circle_marker});
+    use_js!("js_utils/src/rectangle.ts", "assets/dioxus_leaflet.js"::{update_rectangle, delete_rectangle});
+    use_js!("js_utils/src/geojson.ts", "assets/dioxus_leaflet.js"::{update_geojson, on_geojson_feature_click, delete_geojson});
+    use_js!("js_utils/src/layer_group.ts", "assets/dioxus_leaflet.js"::{update_layer_group, get_layer_group_bounds, delete_layer_group});
+    use_js!("js_utils/src/marker_cluster.ts", "assets/dioxus_leaflet.js"::{
+        update_marker_cluster_group, on_cluster_icon, on_cluster_click, delete_marker_cluster_group
+    });
+    use_js!("js_utils/src/tile_layer.ts", "assets/dioxus_leaflet.js"::{update_tile_layer, update_wms_layer, delete_tile_layer});
+    use_js!("js_utils/src/layers_control.ts", "assets/dioxus_leaflet.js"::{
+        update_layers_control, set_layers_control_selection, on_layers_control_change,
+        update_control_layer, delete_control_layer, delete_layers_control
+    });
+    use_js!("js_utils/src/popup.ts", "assets/dioxus_leaflet.js"::{
+        update_popup, update_standalone_popup, close_standalone_popup, on_popup_toggle, delete_popup
+    });
+    use_js!("js_utils/src/tooltip.ts", "assets/dioxus_leaflet.js"::{update_tooltip, delete_tooltip});
+    use_js!("js_utils/src/image_overlay.ts", "assets/dioxus_leaflet.js"::{
+        update_image_overlay, update_video_overlay, update_svg_overlay, delete_image_overlay
+    });
+}
+
+/// Token identifying a call to a JavaScript function, under which the message of the
+/// exception it threw is kept
+#[derive(Clone, Copy, serde::Serialize)]
+struct Call(u64);
+
+impl Call {
+    fn new() -> Self {
+        static NEXT: AtomicU64 = AtomicU64::new(0);
+        Self(NEXT.fetch_add(1, Ordering::Relaxed))
+    }
 }
 
-fn js_to_eval(err: JsError) -> Box<dyn Error + Send + Sync> {
+/// Converts the failure of a JavaScript call, with the message of the exception it threw
+async fn js_error(err: JsError, call: Call) -> LeafletError {
     match err {
-        JsError::Eval { error, .. } => error.into(),
-        JsError::Threw { func, .. } => Box::<dyn Error + Send + Sync>::from(func),
+        JsError::Eval { func, error } => match error.as_ref() {
+            EvalError::Serialization(e) => LeafletError::Serialization(e.to_string()),
+            e => LeafletError::JsException {
+                func: func.to_string(),
+                message: e.to_string(),
+            },
+        },
+        JsError::Threw { func } => LeafletError::JsException {
+            func: func.to_string(),
+            message: js_api::take_js_error(call)
+                .await
+                .ok()
+                .flatten()
+                .unwrap_or_else(|| "threw an exception".to_string()),
+        },
+    }
+}
+
+trait JsResultExt<T> {
+    async fn map_js_error(self, call: Call) -> Result<T, LeafletError>;
+}
+
+impl<T> JsResultExt<T> for Result<T, JsError> {
+    async fn map_js_error(self, call: Call) -> Result<T, LeafletError> {
+        match self {
+            Ok(value) => Ok(value),
+            Err(err) => Err(js_error(err, call).await),
+        }
+    }
+}
+
+/// Converts the failure of a function that needs an existing map or layer, telling whether
+/// it was missing or whether Leaflet was never loaded
+async fn lookup_error<T>(
+    result: Result<T, JsError>,
+    call: Call,
+    map_id: &Id,
+    layer_id: Option<&Id>,
+) -> Result<T, LeafletError> {
+    let error = match result {
+        Ok(value) => return Ok(value),
+        Err(err) => js_error(err, call).await,
+    };
+    if !matches!(error, LeafletError::JsException { .. }) {
+        return Err(error);
+    }
+    if !js_api::is_leaflet_loaded().await.unwrap_or(true) {
+        return Err(LeafletError::LeafletNotLoaded);
+    }
+    if !js_api::map_exists(map_id).await.unwrap_or(true) {
+        return Err(LeafletError::MapNotFound(map_id.clone()));
+    }
+    if let Some(layer_id) = layer_id
+        && !js_api::layer_exists(layer_id).await.unwrap_or(true)
+    {
+        return Err(LeafletError::LayerNotFound(layer_id.clone()));
+    }
+    Err(error)
+}
+
+/// Returns the map or group a layer is added to, refusing layers placed in another kind of layer
+fn container_of(layer_id: &Id) -> Result<&Id, LeafletError> {
+    let parent = layer_id.parent().unwrap();
+    if parent.is_container() {
+        Ok(parent)
+    } else {
+        Err(LeafletError::Misplaced(format!(
+            "{layer_id} must be placed in a Map or a layer group, not in {parent}"
+        )))
+    }
+}
+
+#[derive(serde::Serialize)]
+struct LeafletFiles {
+    css: String,
+    js: String,
+    css_integrity: Option<String>,
+    js_integrity: Option<String>,
+}
+
+/// Waits for Leaflet to be loaded, trying the fallback resources when it takes too long
+pub async fn load_leaflet(options: &MapOptions) -> Result<(), LeafletError> {
+    let fallback = match &options.leaflet_fallback {
+        Some(resources) => {
+            resources.check_integrity()?;
+            Some(LeafletFiles {
+                css: resources.css_url(),
+                js: resources.js_url(),
+                css_integrity: resources.css_integrity(),
+                js_integrity: resources.js_integrity(),
+            })
+        }
+        None => None,
+    };
+    let call = Call::new();
+    let loaded = js_api::load_leaflet(options.leaflet_timeout.as_millis() as f64, fallback, call)
+        .await
+        .map_js_error(call)
+        .await?;
+    if loaded {
+        Ok(())
+    } else {
+        Err(LeafletError::LeafletNotLoaded)
     }
 }
 
@@ -28,33 +175,46 @@ pub async fn update_map(
     id: &Id,
     initial_position: &MapPosition,
     options: &MapOptions,
-) -> Result<(), Box<dyn Error + Send + Sync>> {
-    js_api::update_map(id, initial_position, options)
+) -> Result<(), LeafletError> {
+    let call = Call::new();
+    js_api::update_map(id, initial_position, options, call)
+        .await
+        .map_js_error(call)
         .await
-        .map_err(js_to_eval)
 }
 
-pub async fn delete_map(id: &Id) -> Result<(), Box<dyn Error + Send + Sync>> {
-    js_api::delete_map(id).await.map_err(js_to_eval)
+pub async fn update_map_options(map_id: &Id, options: &MapOptions) -> Result<(), LeafletError> {
+    let call = Call::new();
+    lookup_error(
+        js_api::update_map_options(map_id, options, call).await,
+        call,
+        map_id,
+        None,
+    )
+    .await
 }
 
-pub async fn on_map_click(
-    map_id: &Id,
-    callback: EventHandler<LatLng>,
-) -> Result<(), Box<dyn Error + Send + Sync>> {
+pub async fn delete_map(id: &Id) -> Result<(), LeafletError> {
+    let call = Call::new();
+    js_api::delete_map(id, call).await.map_js_error(call).await
+}
+
+pub async fn on_map_click(map_id: &Id, callback: EventHandler<LatLng>) -> Result<(), LeafletError> {
     let mapper_cb = Callback::new(move |coords: Vec<f64>| async move {
         callback.call(LatLng::new(coords[0], coords[1]));
         Result::<(), SerdeJsonValue>::Ok(())
     });
-    js_api::on_map_click(map_id, mapper_cb)
+    let call = Call::new();
+    js_api::on_map_click(map_id, mapper_cb, call)
+        .await
+        .map_js_error(call)
         .await
-        .map_err(js_to_eval)
 }
 
 pub async fn on_map_move(
     map_id: &Id,
     callback: EventHandler<MapPosition>,
-) -> Result<(), Box<dyn Error + Send + Sync>> {
+) -> Result<(), LeafletError> {
     let mapper_cb = Callback::new(move |data: Vec<f64>| async move {
         let pos = MapPosition {
             coordinates: LatLng::new(data[0], data[1]),
@@ -63,80 +223,778 @@ pub async fn on_map_move(
         callback.call(pos);
         Result::<(), SerdeJsonValue>::Ok(())
     });
-    js_api::on_map_move(map_id, mapper_cb)
+    let call = Call::new();
+    js_api::on_map_move(map_id, mapper_cb, call)
+        .await
+        .map_js_error(call)
+        .await
+}
+
+pub async fn on_map_move_end(
+    map_id: &Id,
+    callback: EventHandler<MapPosition>,
+) -> Result<(), LeafletError> {
+    let mapper_cb = Callback::new(move |data: Vec<f64>| async move {
+        let pos = MapPosition {
+            coordinates: LatLng::new(data[0], data[1]),
+            zoom: data[2],
+        };
+        callback.call(pos);
+        Result::<(), SerdeJsonValue>::Ok(())
+    });
+    let call = Call::new();
+    js_api::on_map_move_end(map_id, mapper_cb, call)
+        .await
+        .map_js_error(call)
+        .await
+}
+
+pub async fn set_map_view(map_id: &Id, position: &MapPosition) -> Result<(), LeafletError> {
+    let call = Call::new();
+    lookup_error(
+        js_api::set_map_view(map_id, position, call).await,
+        call,
+        map_id,
+        None,
+    )
+    .await
+}
+
+pub async fn fly_map_to(map_id: &Id, position: &MapPosition) -> Result<(), LeafletError> {
+    let call = Call::new();
+    lookup_error(
+        js_api::fly_map_to(map_id, position, call).await,
+        call,
+        map_id,
+        None,
+    )
+    .await
+}
+
+pub async fn pan_map_to(map_id: &Id, coordinates: &LatLng) -> Result<(), LeafletError> {
+    let call = Call::new();
+    lookup_error(
+        js_api::pan_map_to(map_id, coordinates, call).await,
+        call,
+        map_id,
+        None,
+    )
+    .await
+}
+
+pub async fn set_map_zoom(map_id: &Id, zoom: f64) -> Result<(), LeafletError> {
+    let call = Call::new();
+    lookup_error(
+        js_api::set_map_zoom(map_id, zoom, call).await,
+        call,
+        map_id,
+        None,
+    )
+    .await
+}
+
+pub async fn zoom_map_in(map_id: &Id) -> Result<(), LeafletError> {
+    let call = Call::new();
+    lookup_error(js_api::zoom_map_in(map_id, call).await, call, map_id, None).await
+}
+
+pub async fn zoom_map_out(map_id: &Id) -> Result<(), LeafletError> {
+    let call = Call::new();
+    lookup_error(js_api::zoom_map_out(map_id, call).await, call, map_id, None).await
+}
+
+pub async fn fit_map_bounds(map_id: &Id, bounds: &LatLngBounds) -> Result<(), LeafletError> {
+    let call = Call::new();
+    lookup_error(
+        js_api::fit_map_bounds(map_id, bounds, call).await,
+        call,
+        map_id,
+        None,
+    )
+    .await
+}
+
+pub async fn invalidate_map_size(map_id: &Id) -> Result<(), LeafletError> {
+    let call = Call::new();
+    lookup_error(
+        js_api::invalidate_map_size(map_id, call).await,
+        call,
+        map_id,
+        None,
+    )
+    .await
+}
+
+pub async fn get_map_center(map_id: &Id) -> Result<LatLng, LeafletError> {
+    let call = Call::new();
+    let center = lookup_error(
+        js_api::get_map_center(map_id, call).await,
+        call,
+        map_id,
+        None,
+    )
+    .await?;
+    Ok(LatLng::new(center[0], center[1]))
+}
+
+pub async fn get_map_zoom(map_id: &Id) -> Result<f64, LeafletError> {
+    let call = Call::new();
+    lookup_error(js_api::get_map_zoom(map_id, call).await, call, map_id, None).await
+}
+
+pub async fn get_map_bounds(map_id: &Id) -> Result<LatLngBounds, LeafletError> {
+    let call = Call::new();
+    let bounds = lookup_error(
+        js_api::get_map_bounds(map_id, call).await,
+        call,
+        map_id,
+        None,
+    )
+    .await?;
+    Ok(LatLngBounds {
+        south_west: LatLng::new(bounds[0], bounds[1]),
+        north_east: LatLng::new(bounds[2], bounds[3]),
+    })
+}
+
+pub async fn on_layer_event(
+    layer_id: &Id,
+    event: &str,
+    callback: EventHandler<LatLng>,
+) -> Result<(), LeafletError> {
+    let mapper_cb = Callback::new(move |coords: Vec<f64>| async move {
+        callback.call(LatLng::new(coords[0], coords[1]));
+        Result::<(), SerdeJsonValue>::Ok(())
+    });
+    let call = Call::new();
+    js_api::on_layer_event(layer_id, event, mapper_cb, call)
+        .await
+        .map_js_error(call)
         .await
-        .map_err(js_to_eval)
 }
 
 pub async fn update_marker(
     marker_id: &Id,
     coordinate: &LatLng,
     icon: &Option<MarkerIcon>,
-) -> Result<(), Box<dyn Error + Send + Sync>> {
+    draggable: bool,
+    custom_data: &Option<HashMap<String, String>>,
+) -> Result<(), LeafletError> {
+    let call = Call::new();
     js_api::update_marker(
-        marker_id.parent().unwrap(),
+        container_of(marker_id)?,
         marker_id.id(),
         coordinate,
         icon,
+        draggable,
+        custom_data,
+        call,
     )
     .await
-    .map_err(js_to_eval)
+    .map_js_error(call)
+    .await
+}
+
+pub async fn delete_marker(marker_id: &Id) -> Result<(), LeafletError> {
+    let call = Call::new();
+    js_api::delete_marker(marker_id.parent().unwrap(), marker_id.id(), call)
+        .await
+        .map_js_error(call)
+        .await
+}
+
+/// Size and anchors of the icon holding the content of a marker
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DivIconOptions {
+    pub icon_size: Option<(u32, u32)>,
+    pub icon_anchor: Option<(i32, i32)>,
+    pub popup_anchor: Option<(i32, i32)>,
+    pub tooltip_anchor: Option<(i32, i32)>,
+    pub class_name: String,
 }
 
-pub async fn delete_marker(marker_id: &Id) -> Result<(), Box<dyn Error + Send + Sync>> {
-    js_api::delete_marker(marker_id.parent().unwrap(), marker_id.id())
+pub async fn update_marker_content(
+    marker_id: &Id,
+    options: &DivIconOptions,
+) -> Result<(), LeafletError> {
+    let call = Call::new();
+    js_api::update_marker_content(marker_id.id(), options, call)
+        .await
+        .map_js_error(call)
+        .await
+}
+
+pub async fn delete_marker_content(marker_id: &Id) -> Result<(), LeafletError> {
+    let call = Call::new();
+    js_api::delete_marker_content(marker_id.id(), call)
+        .await
+        .map_js_error(call)
         .await
-        .map_err(js_to_eval)
 }
 
 pub async fn update_polygon(
     polygon_id: &Id,
     coordinates: &Vec<Vec<Vec<LatLng>>>,
     options: &PathOptions,
-) -> Result<(), Box<dyn Error + Send + Sync>> {
+) -> Result<(), LeafletError> {
+    let call = Call::new();
     js_api::update_polygon(
-        polygon_id.parent().unwrap(),
+        container_of(polygon_id)?,
         polygon_id.id(),
         coordinates,
         options,
+        call,
     )
     .await
-    .map_err(js_to_eval)
+    .map_js_error(call)
+    .await
 }
 
-pub async fn delete_polygon(polygon_id: &Id) -> Result<(), Box<dyn Error + Send + Sync>> {
-    js_api::delete_polygon(polygon_id.parent().unwrap(), polygon_id.id())
+pub async fn delete_polygon(polygon_id: &Id) -> Result<(), LeafletError> {
+    let call = Call::new();
+    js_api::delete_polygon(polygon_id.parent().unwrap(), polygon_id.id(), call)
+        .await
+        .map_js_error(call)
         .await
-        .map_err(js_to_eval)
 }
 
 pub async fn update_polyline(
     polyline_id: &Id,
     coordinates: &[Vec<LatLng>],
     options: &PathOptions,
-) -> Result<(), Box<dyn Error + Send + Sync>> {
+) -> Result<(), LeafletError> {
+    let call = Call::new();
     js_api::update_polyline(
-        polyline_id.parent().unwrap(),
+        container_of(polyline_id)?,
         polyline_id.id(),
         coordinates,
         options,
+        call,
+    )
+    .await
+    .map_js_error(call)
+    .await
+}
+
+pub async fn delete_polyline(polyline_id: &Id) -> Result<(), LeafletError> {
+    let call = Call::new();
+    js_api::delete_polyline(polyline_id.parent().unwrap(), polyline_id.id(), call)
+        .await
+        .map_js_error(call)
+        .await
+}
+
+pub async fn update_circle(
+    circle_id: &Id,
+    center: &LatLng,
+    radius: f64,
+    options: &PathOptions,
+) -> Result<(), LeafletError> {
+    let call = Call::new();
+    js_api::update_circle(
+        container_of(circle_id)?,
+        circle_id.id(),
+        center,
+        radius,
+        options,
+        call,
+    )
+    .await
+    .map_js_error(call)
+    .await
+}
+
+pub async fn delete_circle(circle_id: &Id) -> Result<(), LeafletError> {
+    let call = Call::new();
+    js_api::delete_circle(circle_id.parent().unwrap(), circle_id.id(), call)
+        .await
+        .map_js_error(call)
+        .await
+}
+
+pub async fn update_circle_marker(
+    circle_marker_id: &Id,
+    center: &LatLng,
+    radius: f64,
+    options: &PathOptions,
+) -> Result<(), LeafletError> {
+    let call = Call::new();
+    js_api::update_circle_marker(
+        container_of(circle_marker_id)?,
+        circle_marker_id.id(),
+        center,
+        radius,
+        options,
+        call,
+    )
+    .await
+    .map_js_error(call)
+    .await
+}
+
+pub async fn delete_circle_marker(circle_marker_id: &Id) -> Result<(), LeafletError> {
+    let call = Call::new();
+    js_api::delete_circle_marker(
+        circle_marker_id.parent().unwrap(),
+        circle_marker_id.id(),
+        call,
+    )
+    .await
+    .map_js_error(call)
+    .await
+}
+
+pub async fn update_rectangle(
+    rectangle_id: &Id,
+    bounds: &LatLngBounds,
+    options: &PathOptions,
+) -> Result<(), LeafletError> {
+    let call = Call::new();
+    js_api::update_rectangle(
+        container_of(rectangle_id)?,
+        rectangle_id.id(),
+        bounds,
+        options,
+        call,
+    )
+    .await
+    .map_js_error(call)
+    .await
+}
+
+pub async fn delete_rectangle(rectangle_id: &Id) -> Result<(), LeafletError> {
+    let call = Call::new();
+    js_api::delete_rectangle(rectangle_id.parent().unwrap(), rectangle_id.id(), call)
+        .await
+        .map_js_error(call)
+        .await
+}
+
+/// GeoJSON feature along with the style and point layer computed for it
+#[derive(serde::Serialize)]
+pub struct GeoJsonItem {
+    pub feature: Feature,
+    pub style: Option<PathOptions>,
+    pub point: Option<PointLayer>,
+}
+
+pub async fn update_geojson(geojson_id: &Id, items: &[GeoJsonItem]) -> Result<(), LeafletError> {
+    let call = Call::new();
+    js_api::update_geojson(container_of(geojson_id)?, geojson_id.id(), items, call)
+        .await
+        .map_js_error(call)
+        .await
+}
+
+pub async fn on_geojson_feature_click(
+    geojson_id: &Id,
+    callback: EventHandler<serde_json::Map<String, SerdeJsonValue>>,
+) -> Result<(), LeafletError> {
+    let mapper_cb = Callback::new(move |properties: SerdeJsonValue| async move {
+        if let SerdeJsonValue::Object(properties) = properties {
+            callback.call(properties);
+        }
+        Result::<(), SerdeJsonValue>::Ok(())
+    });
+    let call = Call::new();
+    js_api::on_geojson_feature_click(geojson_id.id(), mapper_cb, call)
+        .await
+        .map_js_error(call)
+        .await
+}
+
+pub async fn delete_geojson(geojson_id: &Id) -> Result<(), LeafletError> {
+    let call = Call::new();
+    js_api::delete_geojson(geojson_id.parent().unwrap(), geojson_id.id(), call)
+        .await
+        .map_js_error(call)
+        .await
+}
+
+pub async fn update_layer_group(group_id: &Id, visible: bool) -> Result<(), LeafletError> {
+    let feature_group = matches!(group_id, Id::FeatureGroup { .. });
+    let call = Call::new();
+    js_api::update_layer_group(
+        container_of(group_id)?,
+        group_id.id(),
+        feature_group,
+        visible,
+        call,
+    )
+    .await
+    .map_js_error(call)
+    .await
+}
+
+pub async fn get_layer_group_bounds(group_id: &Id) -> Result<Option<LatLngBounds>, LeafletError> {
+    let call = Call::new();
+    let bounds = lookup_error(
+        js_api::get_layer_group_bounds(group_id.id(), call).await,
+        call,
+        group_id.map_id(),
+        Some(group_id),
+    )
+    .await?;
+    Ok(bounds.map(|bounds| LatLngBounds {
+        south_west: LatLng::new(bounds[0], bounds[1]),
+        north_east: LatLng::new(bounds[2], bounds[3]),
+    }))
+}
+
+pub async fn delete_layer_group(group_id: &Id) -> Result<(), LeafletError> {
+    let call = Call::new();
+    js_api::delete_layer_group(group_id.parent().unwrap(), group_id.id(), call)
+        .await
+        .map_js_error(call)
+        .await
+}
+
+pub async fn update_marker_cluster_group(
+    group_id: &Id,
+    script_url: &str,
+    script_integrity: Option<&str>,
+    options: &MarkerClusterOptions,
+) -> Result<(), LeafletError> {
+    let options = serde_json::to_value(options)?;
+    let call = Call::new();
+    js_api::update_marker_cluster_group(
+        container_of(group_id)?,
+        group_id.id(),
+        script_url,
+        script_integrity,
+        &options,
+        call,
+    )
+    .await
+    .map_js_error(call)
+    .await
+}
+
+pub async fn on_cluster_icon(
+    group_id: &Id,
+    callback: Callback<usize, ClusterIcon>,
+) -> Result<(), LeafletError> {
+    let mapper_cb = Callback::new(move |count: f64| async move {
+        serde_json::to_value(callback.call(count as usize))
+            .map_err(|e| SerdeJsonValue::String(e.to_string()))
+    });
+    let call = Call::new();
+    js_api::on_cluster_icon(group_id.id(), mapper_cb, call)
+        .await
+        .map_js_error(call)
+        .await
+}
+
+pub async fn on_cluster_click(
+    group_id: &Id,
+    callback: EventHandler<Vec<HashMap<String, String>>>,
+) -> Result<(), LeafletError> {
+    let mapper_cb = Callback::new(move |data: SerdeJsonValue| async move {
+        let data =
+            serde_json::from_value(data).map_err(|e| SerdeJsonValue::String(e.to_string()))?;
+        callback.call(data);
+        Result::<(), SerdeJsonValue>::Ok(())
+    });
+    let call = Call::new();
+    js_api::on_cluster_click(group_id.id(), mapper_cb, call)
+        .await
+        .map_js_error(call)
+        .await
+}
+
+pub async fn delete_marker_cluster_group(group_id: &Id) -> Result<(), LeafletError> {
+    let call = Call::new();
+    js_api::delete_marker_cluster_group(group_id.parent().unwrap(), group_id.id(), call)
+        .await
+        .map_js_error(call)
+        .await
+}
+
+pub async fn update_tile_layer(
+    layer_id: &Id,
+    layer: &TileLayerOptions,
+    opacity: Option<f64>,
+    z_index: Option<i32>,
+) -> Result<(), LeafletError> {
+    let call = Call::new();
+    js_api::update_tile_layer(
+        container_of(layer_id)?,
+        layer_id.id(),
+        layer,
+        opacity,
+        z_index.map(f64::from),
+        call,
+    )
+    .await
+    .map_js_error(call)
+    .await
+}
+
+pub async fn update_wms_layer(
+    layer_id: &Id,
+    url: &str,
+    options: &WmsOptions,
+    opacity: f64,
+    z_index: i32,
+) -> Result<(), LeafletError> {
+    let call = Call::new();
+    js_api::update_wms_layer(
+        container_of(layer_id)?,
+        layer_id.id(),
+        url,
+        options,
+        opacity,
+        z_index as f64,
+        call,
     )
     .await
-    .map_err(js_to_eval)
+    .map_js_error(call)
+    .await
+}
+
+pub async fn delete_tile_layer(layer_id: &Id) -> Result<(), LeafletError> {
+    let call = Call::new();
+    js_api::delete_tile_layer(layer_id.parent().unwrap(), layer_id.id(), call)
+        .await
+        .map_js_error(call)
+        .await
 }
 
-pub async fn delete_polyline(polyline_id: &Id) -> Result<(), Box<dyn Error + Send + Sync>> {
-    js_api::delete_polyline(polyline_id.parent().unwrap(), polyline_id.id())
+pub async fn update_layers_control(control_id: &Id, collapsed: bool) -> Result<(), LeafletError> {
+    let call = Call::new();
+    js_api::update_layers_control(control_id.map_id(), control_id.id(), collapsed, call)
+        .await
+        .map_js_error(call)
+        .await
+}
+
+pub async fn set_layers_control_selection(
+    control_id: &Id,
+    base: Option<&str>,
+    overlays: Option<&[String]>,
+) -> Result<(), LeafletError> {
+    let call = Call::new();
+    js_api::set_layers_control_selection(control_id.id(), base, overlays, call)
+        .await
+        .map_js_error(call)
+        .await
+}
+
+/// Layers shown by a layers control, as reported by Leaflet
+#[derive(serde::Deserialize)]
+pub struct LayersControlSelection {
+    pub base: Option<String>,
+    pub overlays: Vec<String>,
+}
+
+pub async fn on_layers_control_change(
+    control_id: &Id,
+    callback: EventHandler<LayersControlSelection>,
+) -> Result<(), LeafletError> {
+    let mapper_cb = Callback::new(move |selection: SerdeJsonValue| async move {
+        let selection =
+            serde_json::from_value(selection).map_err(|e| SerdeJsonValue::String(e.to_string()))?;
+        callback.call(selection);
+        Result::<(), SerdeJsonValue>::Ok(())
+    });
+    let call = Call::new();
+    js_api::on_layers_control_change(control_id.id(), mapper_cb, call)
+        .await
+        .map_js_error(call)
+        .await
+}
+
+pub async fn delete_layers_control(control_id: &Id) -> Result<(), LeafletError> {
+    let call = Call::new();
+    js_api::delete_layers_control(control_id.id(), call)
+        .await
+        .map_js_error(call)
+        .await
+}
+
+pub async fn update_control_layer(
+    layer_id: &Id,
+    name: &str,
+    overlay: bool,
+) -> Result<(), LeafletError> {
+    let call = Call::new();
+    js_api::update_control_layer(
+        layer_id.parent().unwrap(),
+        layer_id.id(),
+        name,
+        overlay,
+        call,
+    )
+    .await
+    .map_js_error(call)
+    .await
+}
+
+pub async fn delete_control_layer(layer_id: &Id) -> Result<(), LeafletError> {
+    let call = Call::new();
+    js_api::delete_control_layer(layer_id.parent().unwrap(), layer_id.id(), call)
+        .await
+        .map_js_error(call)
         .await
-        .map_err(js_to_eval)
 }
 
 pub async fn update_popup(
     popup_id: &Id,
     options: &PopupOptions,
-) -> Result<(), Box<dyn Error + Send + Sync>> {
-    let marker_id = popup_id.parent().unwrap();
-    js_api::update_popup(marker_id, popup_id, options)
+    open: Option<bool>,
+) -> Result<(), LeafletError> {
+    let layer_id = popup_id.parent().unwrap();
+    let call = Call::new();
+    js_api::update_popup(layer_id, popup_id, options, open, call)
+        .await
+        .map_js_error(call)
+        .await
+}
+
+pub async fn update_standalone_popup(
+    popup_id: &Id,
+    position: &LatLng,
+    options: &PopupOptions,
+    open: Option<bool>,
+) -> Result<(), LeafletError> {
+    let call = Call::new();
+    js_api::update_standalone_popup(popup_id.map_id(), popup_id, position, options, open, call)
+        .await
+        .map_js_error(call)
+        .await
+}
+
+pub async fn close_standalone_popup(popup_id: &Id) -> Result<(), LeafletError> {
+    let call = Call::new();
+    js_api::close_standalone_popup(popup_id, call)
+        .await
+        .map_js_error(call)
+        .await
+}
+
+pub async fn on_popup_toggle(
+    popup_id: &Id,
+    callback: EventHandler<bool>,
+) -> Result<(), LeafletError> {
+    let mapper_cb = Callback::new(move |open: bool| async move {
+        callback.call(open);
+        Result::<(), SerdeJsonValue>::Ok(())
+    });
+    let call = Call::new();
+    js_api::on_popup_toggle(popup_id, mapper_cb, call)
+        .await
+        .map_js_error(call)
+        .await
+}
+
+pub async fn delete_popup(popup_id: &Id) -> Result<(), LeafletError> {
+    let call = Call::new();
+    js_api::delete_popup(popup_id.parent().unwrap(), popup_id, call)
+        .await
+        .map_js_error(call)
+        .await
+}
+
+pub async fn update_tooltip(tooltip_id: &Id, options: &TooltipOptions) -> Result<(), LeafletError> {
+    let call = Call::new();
+    js_api::update_tooltip(tooltip_id.parent().unwrap(), tooltip_id, options, call)
+        .await
+        .map_js_error(call)
+        .await
+}
+
+pub async fn delete_tooltip(tooltip_id: &Id) -> Result<(), LeafletError> {
+    let call = Call::new();
+    js_api::delete_tooltip(tooltip_id.parent().unwrap(), tooltip_id, call)
+        .await
+        .map_js_error(call)
+        .await
+}
+
+/// Options shared by image, video and SVG overlays
+#[derive(serde::Serialize)]
+pub struct OverlayOptions {
+    pub opacity: f64,
+    pub z_index: i32,
+    pub interactive: bool,
+    pub class_name: Option<String>,
+    pub alt: Option<String>,
+    pub cross_origin: Option<CrossOrigin>,
+}
+
+#[derive(serde::Serialize)]
+pub struct VideoOptions {
+    pub autoplay: bool,
+    pub looped: bool,
+    pub muted: bool,
+    pub keep_aspect_ratio: bool,
+}
+
+pub async fn update_image_overlay(
+    overlay_id: &Id,
+    url: &str,
+    bounds: &LatLngBounds,
+    options: &OverlayOptions,
+) -> Result<(), LeafletError> {
+    let call = Call::new();
+    js_api::update_image_overlay(
+        container_of(overlay_id)?,
+        overlay_id.id(),
+        url,
+        bounds,
+        options,
+        call,
+    )
+    .await
+    .map_js_error(call)
+    .await
+}
+
+pub async fn update_video_overlay(
+    overlay_id: &Id,
+    urls: &[String],
+    bounds: &LatLngBounds,
+    options: &OverlayOptions,
+    video: &VideoOptions,
+) -> Result<(), LeafletError> {
+    let call = Call::new();
+    js_api::update_video_overlay(
+        container_of(overlay_id)?,
+        overlay_id.id(),
+        urls,
+        bounds,
+        options,
+        video,
+        call,
+    )
+    .await
+    .map_js_error(call)
+    .await
+}
+
+pub async fn update_svg_overlay(
+    overlay_id: &Id,
+    bounds: &LatLngBounds,
+    options: &OverlayOptions,
+) -> Result<(), LeafletError> {
+    let call = Call::new();
+    js_api::update_svg_overlay(
+        container_of(overlay_id)?,
+        overlay_id.id(),
+        bounds,
+        options,
+        call,
+    )
+    .await
+    .map_js_error(call)
+    .await
+}
+
+pub async fn delete_image_overlay(overlay_id: &Id) -> Result<(), LeafletError> {
+    let call = Call::new();
+    js_api::delete_image_overlay(overlay_id.parent().unwrap(), overlay_id.id(), call)
+        .await
+        .map_js_error(call)
         .await
-        .map_err(js_to_eval)
 }
@@ -83,12 +83,21 @@
 //! }
 //! ```
 mod components;
+mod hooks;
 mod interop;
 mod types;
 
 // Re-export main types and components
-pub use components::{Map, Marker, Polygon, Polyline, Popup};
+pub use components::{
+    BaseLayer, Circle, CircleMarker, FeatureFn, FeatureGroup, FeatureGroupHandle, GeoJson,
+    ImageOverlay, LayerGroup, LayersControl, Map, Marker, MarkerClusterGroup, MarkerContent,
+    Overlay, Polygon, Polyline, Popup, Rectangle, SvgOverlay, TileLayer, Tooltip, VideoOverlay,
+    WmsLayer,
+};
+pub use hooks::{MapHandle, use_map};
 pub use types::{
-    Color, LatLng, LeafletResources, LineCap, LineJoin, MapOptions, MapPosition, MarkerIcon,
-    PathOptions, PopupOptions, TileLayer,
+    ClusterIcon, Color, CrossOrigin, Crs, Feature, FeatureCollection, Geometry, LatLng,
+    LatLngBounds, LeafletError, LeafletResources, LineCap, LineJoin, MapOptions, MapPosition,
+    MarkerClusterIntegrity, MarkerClusterOptions, MarkerClusterResources, MarkerIcon, PathOptions,
+    PointLayer, PopupOptions, TileLayerOptions, TooltipDirection, TooltipOptions, WmsOptions,
 };
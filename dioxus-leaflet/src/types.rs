@@ -7,20 +7,44 @@ pub use marker_icon::MarkerIcon;
 mod popup_options;
 pub use popup_options::PopupOptions;
 
+mod tooltip_options;
+pub use tooltip_options::{TooltipDirection, TooltipOptions};
+
+mod marker_cluster_options;
+pub use marker_cluster_options::{ClusterIcon, MarkerClusterOptions};
+
+mod marker_cluster_resources;
+pub use marker_cluster_resources::{MarkerClusterIntegrity, MarkerClusterResources};
+
+mod crs;
+pub use crs::Crs;
+
 mod map_options;
 pub use map_options::MapOptions;
 
 mod tile_layer;
-pub use tile_layer::TileLayer;
+pub use tile_layer::{CrossOrigin, TileLayerOptions};
+
+mod wms_options;
+pub use wms_options::WmsOptions;
 
 mod leaflet_resources;
 pub use leaflet_resources::LeafletResources;
 
+mod leaflet_error;
+pub use leaflet_error::LeafletError;
+
 mod path_options;
 pub use path_options::{Color, LineCap, LineJoin, PathOptions};
 
 mod latlng;
 pub use latlng::LatLng;
 
+mod latlng_bounds;
+pub use latlng_bounds::LatLngBounds;
+
+mod geojson;
+pub use geojson::{Feature, FeatureCollection, Geometry, PointLayer};
+
 mod id;
 pub use id::*;
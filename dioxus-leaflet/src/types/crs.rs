@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+
+/// Coordinate reference system of a map, deciding how coordinates are projected on screen
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum Crs {
+    /// Web Mercator, used by most tile providers
+    #[default]
+    EPSG3857,
+    /// Equirectangular projection of longitude and latitude
+    EPSG4326,
+    /// Elliptical Mercator, used by a few tile providers like Yandex
+    EPSG3395,
+    /// Flat plane where `LatLng::new(y, x)` maps to pixel `(x, -y)` at zoom 0,
+    /// for floor plans and other non-geographic images
+    Simple,
+    /// Flat plane like `Simple` where `LatLng::new(y, x)` maps to pixel `(a * x + b, c * y + d)` at zoom 0
+    Custom { a: f64, b: f64, c: f64, d: f64 },
+}
+
+impl Crs {
+    /// Flat CRS where `LatLng::new(y, x)` maps to pixel `(x, y)` at zoom 0, so that image
+    /// coordinates can be used as they are, with the Y axis pointing down
+    pub fn pixels() -> Self {
+        Self::Custom {
+            a: 1.0,
+            b: 0.0,
+            c: 1.0,
+            d: 0.0,
+        }
+    }
+}
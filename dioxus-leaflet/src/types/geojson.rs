@@ -0,0 +1,111 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::str::FromStr;
+
+use crate::{LatLng, MarkerIcon, PathOptions};
+
+/// GeoJSON position, in `[longitude, latitude]` or `[longitude, latitude, altitude]` order
+pub type Position = Vec<f64>;
+
+/// GeoJSON geometry object
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum Geometry {
+    Point {
+        coordinates: Position,
+    },
+    MultiPoint {
+        coordinates: Vec<Position>,
+    },
+    LineString {
+        coordinates: Vec<Position>,
+    },
+    MultiLineString {
+        coordinates: Vec<Vec<Position>>,
+    },
+    Polygon {
+        coordinates: Vec<Vec<Position>>,
+    },
+    MultiPolygon {
+        coordinates: Vec<Vec<Vec<Position>>>,
+    },
+    GeometryCollection {
+        geometries: Vec<Geometry>,
+    },
+}
+
+impl Geometry {
+    /// Returns the position of a `Point` geometry
+    pub fn as_point(&self) -> Option<LatLng> {
+        match self {
+            Geometry::Point { coordinates } if coordinates.len() >= 2 => {
+                Some(LatLng::new(coordinates[1], coordinates[0]))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// GeoJSON feature, a geometry with arbitrary properties
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename = "Feature")]
+pub struct Feature {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<Value>,
+    pub geometry: Option<Geometry>,
+    #[serde(default)]
+    pub properties: Option<Map<String, Value>>,
+}
+
+impl Feature {
+    /// Returns the value of a property, if present
+    pub fn property(&self, key: &str) -> Option<&Value> {
+        self.properties.as_ref()?.get(key)
+    }
+}
+
+/// GeoJSON feature collection
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename = "FeatureCollection")]
+pub struct FeatureCollection {
+    pub features: Vec<Feature>,
+}
+
+impl FeatureCollection {
+    pub fn new(features: Vec<Feature>) -> Self {
+        Self { features }
+    }
+}
+
+impl TryFrom<Value> for FeatureCollection {
+    type Error = serde_json::Error;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        serde_json::from_value(value)
+    }
+}
+
+impl FromStr for FeatureCollection {
+    type Err = serde_json::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_json::from_str(s)
+    }
+}
+
+/// Layer used to display a GeoJSON point feature
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PointLayer {
+    /// A marker, with the default icon if none is given
+    Marker { icon: Option<MarkerIcon> },
+
+    /// A circle marker with a radius in pixels
+    CircleMarker { radius: f64, options: PathOptions },
+}
+
+impl Default for PointLayer {
+    fn default() -> Self {
+        PointLayer::Marker { icon: None }
+    }
+}
@@ -7,7 +7,19 @@ pub enum Id {
     Marker { parent: Rc<Id>, id: usize },
     Polygon { parent: Rc<Id>, id: usize },
     Polyline { parent: Rc<Id>, id: usize },
+    Circle { parent: Rc<Id>, id: usize },
+    CircleMarker { parent: Rc<Id>, id: usize },
+    Rectangle { parent: Rc<Id>, id: usize },
+    GeoJson { parent: Rc<Id>, id: usize },
+    LayerGroup { parent: Rc<Id>, id: usize },
+    FeatureGroup { parent: Rc<Id>, id: usize },
+    MarkerClusterGroup { parent: Rc<Id>, id: usize },
+    TileLayer { parent: Rc<Id>, id: usize },
+    LayersControl { parent: Rc<Id>, id: usize },
+    ControlLayer { parent: Rc<Id>, id: usize },
     Popup { parent: Rc<Id>, id: usize },
+    Tooltip { parent: Rc<Id>, id: usize },
+    ImageOverlay { parent: Rc<Id>, id: usize },
 }
 
 impl Id {
@@ -36,6 +48,76 @@ impl Id {
         }
     }
 
+    pub fn circle(parent: &Rc<Id>, id: usize) -> Id {
+        Id::Circle {
+            parent: parent.clone(),
+            id,
+        }
+    }
+
+    pub fn circle_marker(parent: &Rc<Id>, id: usize) -> Id {
+        Id::CircleMarker {
+            parent: parent.clone(),
+            id,
+        }
+    }
+
+    pub fn rectangle(parent: &Rc<Id>, id: usize) -> Id {
+        Id::Rectangle {
+            parent: parent.clone(),
+            id,
+        }
+    }
+
+    pub fn geojson(parent: &Rc<Id>, id: usize) -> Id {
+        Id::GeoJson {
+            parent: parent.clone(),
+            id,
+        }
+    }
+
+    pub fn layer_group(parent: &Rc<Id>, id: usize) -> Id {
+        Id::LayerGroup {
+            parent: parent.clone(),
+            id,
+        }
+    }
+
+    pub fn feature_group(parent: &Rc<Id>, id: usize) -> Id {
+        Id::FeatureGroup {
+            parent: parent.clone(),
+            id,
+        }
+    }
+
+    pub fn marker_cluster_group(parent: &Rc<Id>, id: usize) -> Id {
+        Id::MarkerClusterGroup {
+            parent: parent.clone(),
+            id,
+        }
+    }
+
+    pub fn tile_layer(parent: &Rc<Id>, id: usize) -> Id {
+        Id::TileLayer {
+            parent: parent.clone(),
+            id,
+        }
+    }
+
+    pub fn layers_control(parent: &Rc<Id>, id: usize) -> Id {
+        Id::LayersControl {
+            parent: parent.clone(),
+            id,
+        }
+    }
+
+    pub fn control_layer(parent: &Rc<Id>, id: usize) -> Id {
+        Id::ControlLayer {
+            parent: parent.clone(),
+            id,
+        }
+    }
+
     pub fn popup(parent: &Rc<Id>, id: usize) -> Id {
         Id::Popup {
             parent: parent.clone(),
@@ -43,13 +125,39 @@ impl Id {
         }
     }
 
+    pub fn tooltip(parent: &Rc<Id>, id: usize) -> Id {
+        Id::Tooltip {
+            parent: parent.clone(),
+            id,
+        }
+    }
+
+    pub fn image_overlay(parent: &Rc<Id>, id: usize) -> Id {
+        Id::ImageOverlay {
+            parent: parent.clone(),
+            id,
+        }
+    }
+
     pub fn id(&self) -> usize {
         match self {
             Id::Map { id }
             | Id::Marker { id, .. }
             | Id::Polygon { id, .. }
             | Id::Polyline { id, .. }
-            | Id::Popup { id, .. } => *id,
+            | Id::Circle { id, .. }
+            | Id::CircleMarker { id, .. }
+            | Id::Rectangle { id, .. }
+            | Id::GeoJson { id, .. }
+            | Id::LayerGroup { id, .. }
+            | Id::FeatureGroup { id, .. }
+            | Id::MarkerClusterGroup { id, .. }
+            | Id::TileLayer { id, .. }
+            | Id::LayersControl { id, .. }
+            | Id::ControlLayer { id, .. }
+            | Id::Popup { id, .. }
+            | Id::Tooltip { id, .. }
+            | Id::ImageOverlay { id, .. } => *id,
         }
     }
 
@@ -59,7 +167,38 @@ impl Id {
             Id::Marker { parent, .. }
             | Id::Polygon { parent, .. }
             | Id::Polyline { parent, .. }
-            | Id::Popup { parent, .. } => Some(parent.as_ref()),
+            | Id::Circle { parent, .. }
+            | Id::CircleMarker { parent, .. }
+            | Id::Rectangle { parent, .. }
+            | Id::GeoJson { parent, .. }
+            | Id::LayerGroup { parent, .. }
+            | Id::FeatureGroup { parent, .. }
+            | Id::MarkerClusterGroup { parent, .. }
+            | Id::TileLayer { parent, .. }
+            | Id::LayersControl { parent, .. }
+            | Id::ControlLayer { parent, .. }
+            | Id::Popup { parent, .. }
+            | Id::Tooltip { parent, .. }
+            | Id::ImageOverlay { parent, .. } => Some(parent.as_ref()),
+        }
+    }
+
+    /// Whether layers can be added to this one, like to a map or a layer group
+    pub fn is_container(&self) -> bool {
+        matches!(
+            self,
+            Id::Map { .. }
+                | Id::LayerGroup { .. }
+                | Id::FeatureGroup { .. }
+                | Id::MarkerClusterGroup { .. }
+                | Id::ControlLayer { .. }
+        )
+    }
+
+    pub fn map_id(&self) -> &Id {
+        match self.parent() {
+            Some(parent) => parent.map_id(),
+            None => self,
         }
     }
 }
@@ -71,7 +210,19 @@ impl From<Id> for f64 {
             | Id::Marker { id, .. }
             | Id::Polygon { id, .. }
             | Id::Polyline { id, .. }
-            | Id::Popup { id, .. } => id as f64,
+            | Id::Circle { id, .. }
+            | Id::CircleMarker { id, .. }
+            | Id::Rectangle { id, .. }
+            | Id::GeoJson { id, .. }
+            | Id::LayerGroup { id, .. }
+            | Id::FeatureGroup { id, .. }
+            | Id::MarkerClusterGroup { id, .. }
+            | Id::TileLayer { id, .. }
+            | Id::LayersControl { id, .. }
+            | Id::ControlLayer { id, .. }
+            | Id::Popup { id, .. }
+            | Id::Tooltip { id, .. }
+            | Id::ImageOverlay { id, .. } => id as f64,
         }
     }
 }
@@ -86,7 +237,19 @@ impl Serialize for Id {
             | Id::Marker { id, .. }
             | Id::Polygon { id, .. }
             | Id::Polyline { id, .. }
-            | Id::Popup { id, .. } => id.serialize(serializer),
+            | Id::Circle { id, .. }
+            | Id::CircleMarker { id, .. }
+            | Id::Rectangle { id, .. }
+            | Id::GeoJson { id, .. }
+            | Id::LayerGroup { id, .. }
+            | Id::FeatureGroup { id, .. }
+            | Id::MarkerClusterGroup { id, .. }
+            | Id::TileLayer { id, .. }
+            | Id::LayersControl { id, .. }
+            | Id::ControlLayer { id, .. }
+            | Id::Popup { id, .. }
+            | Id::Tooltip { id, .. }
+            | Id::ImageOverlay { id, .. } => id.serialize(serializer),
         }
     }
 }
@@ -98,7 +261,19 @@ impl fmt::Display for Id {
             Id::Marker { id, .. } => write!(f, "marker-{id}"),
             Id::Polygon { id, .. } => write!(f, "polygon-{id}"),
             Id::Polyline { id, .. } => write!(f, "polyline-{id}"),
+            Id::Circle { id, .. } => write!(f, "circle-{id}"),
+            Id::CircleMarker { id, .. } => write!(f, "circle-marker-{id}"),
+            Id::Rectangle { id, .. } => write!(f, "rectangle-{id}"),
+            Id::GeoJson { id, .. } => write!(f, "geojson-{id}"),
+            Id::LayerGroup { id, .. } => write!(f, "layer-group-{id}"),
+            Id::FeatureGroup { id, .. } => write!(f, "feature-group-{id}"),
+            Id::MarkerClusterGroup { id, .. } => write!(f, "marker-cluster-group-{id}"),
+            Id::TileLayer { id, .. } => write!(f, "tile-layer-{id}"),
+            Id::LayersControl { id, .. } => write!(f, "layers-control-{id}"),
+            Id::ControlLayer { id, .. } => write!(f, "control-layer-{id}"),
             Id::Popup { id, .. } => write!(f, "popup-{id}"),
+            Id::Tooltip { id, .. } => write!(f, "tooltip-{id}"),
+            Id::ImageOverlay { id, .. } => write!(f, "image-overlay-{id}"),
         }
     }
 }
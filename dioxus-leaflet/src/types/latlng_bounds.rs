@@ -0,0 +1,163 @@
+use crate::LatLng;
+use serde::{Deserialize, Serialize};
+
+/// Rectangular geographical area defined by its south-west and north-east corners
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LatLngBounds {
+    pub south_west: LatLng,
+    pub north_east: LatLng,
+}
+
+impl LatLngBounds {
+    /// Creates new bounds from two opposite corners, in any order
+    pub fn new(corner1: LatLng, corner2: LatLng) -> Self {
+        let mut bounds = Self {
+            south_west: corner1,
+            north_east: corner1,
+        };
+        bounds.extend(corner2);
+        bounds
+    }
+
+    /// Creates the smallest bounds containing all the given points, or `None` if there are none
+    pub fn from_points(points: impl IntoIterator<Item = LatLng>) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        let mut bounds = Self::new(first, first);
+        for point in points {
+            bounds.extend(point);
+        }
+        Some(bounds)
+    }
+
+    /// Returns the north-west corner
+    pub fn north_west(&self) -> LatLng {
+        LatLng::new(self.north_east.lat, self.south_west.lng)
+    }
+
+    /// Returns the south-east corner
+    pub fn south_east(&self) -> LatLng {
+        LatLng::new(self.south_west.lat, self.north_east.lng)
+    }
+
+    /// Returns the center point of the bounds
+    pub fn center(&self) -> LatLng {
+        LatLng::new(
+            (self.south_west.lat + self.north_east.lat) / 2.0,
+            (self.south_west.lng + self.north_east.lng) / 2.0,
+        )
+    }
+
+    /// Returns true if the point is inside the bounds (edges included)
+    pub fn contains(&self, point: LatLng) -> bool {
+        point.lat >= self.south_west.lat
+            && point.lat <= self.north_east.lat
+            && point.lng >= self.south_west.lng
+            && point.lng <= self.north_east.lng
+    }
+
+    /// Returns true if the other bounds are entirely inside these bounds
+    pub fn contains_bounds(&self, other: &LatLngBounds) -> bool {
+        self.contains(other.south_west) && self.contains(other.north_east)
+    }
+
+    /// Returns true if the bounds share at least one point with the other bounds
+    pub fn intersects(&self, other: &LatLngBounds) -> bool {
+        other.north_east.lat >= self.south_west.lat
+            && other.south_west.lat <= self.north_east.lat
+            && other.north_east.lng >= self.south_west.lng
+            && other.south_west.lng <= self.north_east.lng
+    }
+
+    /// Extends the bounds so that they contain the given point
+    pub fn extend(&mut self, point: LatLng) {
+        self.south_west.lat = self.south_west.lat.min(point.lat);
+        self.south_west.lng = self.south_west.lng.min(point.lng);
+        self.north_east.lat = self.north_east.lat.max(point.lat);
+        self.north_east.lng = self.north_east.lng.max(point.lng);
+    }
+
+    /// Extends the bounds so that they contain the other bounds
+    pub fn extend_bounds(&mut self, other: &LatLngBounds) {
+        self.extend(other.south_west);
+        self.extend(other.north_east);
+    }
+
+    /// Returns bounds grown in every direction by the given ratio of their size.
+    /// A negative ratio shrinks them.
+    pub fn pad(&self, ratio: f64) -> Self {
+        let lat_buffer = (self.north_east.lat - self.south_west.lat).abs() * ratio;
+        let lng_buffer = (self.north_east.lng - self.south_west.lng).abs() * ratio;
+        Self {
+            south_west: LatLng::new(
+                self.south_west.lat - lat_buffer,
+                self.south_west.lng - lng_buffer,
+            ),
+            north_east: LatLng::new(
+                self.north_east.lat + lat_buffer,
+                self.north_east.lng + lng_buffer,
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bounds() -> LatLngBounds {
+        LatLngBounds::new(LatLng::new(10.0, 20.0), LatLng::new(0.0, 0.0))
+    }
+
+    #[test]
+    fn new_orders_corners() {
+        let bounds = bounds();
+        assert_eq!(bounds.south_west, LatLng::new(0.0, 0.0));
+        assert_eq!(bounds.north_east, LatLng::new(10.0, 20.0));
+        assert_eq!(bounds.center(), LatLng::new(5.0, 10.0));
+    }
+
+    #[test]
+    fn contains_includes_edges() {
+        let bounds = bounds();
+        assert!(bounds.contains(LatLng::new(5.0, 10.0)));
+        assert!(bounds.contains(LatLng::new(10.0, 0.0)));
+        assert!(!bounds.contains(LatLng::new(10.1, 5.0)));
+        assert!(!bounds.contains(LatLng::new(5.0, -0.1)));
+    }
+
+    #[test]
+    fn intersects_touching_and_overlapping_bounds() {
+        let bounds = bounds();
+        let overlapping = LatLngBounds::new(LatLng::new(5.0, 15.0), LatLng::new(15.0, 25.0));
+        let touching = LatLngBounds::new(LatLng::new(10.0, 20.0), LatLng::new(12.0, 22.0));
+        let apart = LatLngBounds::new(LatLng::new(11.0, 0.0), LatLng::new(12.0, 20.0));
+        assert!(bounds.intersects(&overlapping));
+        assert!(bounds.intersects(&touching));
+        assert!(!bounds.intersects(&apart));
+    }
+
+    #[test]
+    fn extend_grows_to_the_point() {
+        let mut bounds = bounds();
+        bounds.extend(LatLng::new(-5.0, 30.0));
+        assert_eq!(bounds.south_west, LatLng::new(-5.0, 0.0));
+        assert_eq!(bounds.north_east, LatLng::new(10.0, 30.0));
+
+        bounds.extend(LatLng::new(1.0, 1.0));
+        assert_eq!(bounds.south_west, LatLng::new(-5.0, 0.0));
+        assert_eq!(bounds.north_east, LatLng::new(10.0, 30.0));
+    }
+
+    #[test]
+    fn pad_grows_and_shrinks() {
+        let padded = bounds().pad(0.5);
+        assert_eq!(padded.south_west, LatLng::new(-5.0, -10.0));
+        assert_eq!(padded.north_east, LatLng::new(15.0, 30.0));
+        assert_eq!(padded.center(), bounds().center());
+
+        let shrunk = bounds().pad(-0.25);
+        assert_eq!(shrunk.south_west, LatLng::new(2.5, 5.0));
+        assert_eq!(shrunk.north_east, LatLng::new(7.5, 15.0));
+    }
+}
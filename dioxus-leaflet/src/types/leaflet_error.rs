@@ -0,0 +1,67 @@
+use std::{error::Error, fmt};
+
+use super::Id;
+
+/// Error reported by the map components and handles
+#[derive(Debug, Clone, PartialEq)]
+pub enum LeafletError {
+    /// Leaflet could not be loaded from its resources, nor from the fallback ones, in time
+    LeafletNotLoaded,
+
+    /// Strict integrity checking refused to load Leaflet without a known hash
+    MissingIntegrity { version: String },
+
+    /// Strict `MarkerClusterResources` were used without integrity hashes
+    MissingMarkerClusterIntegrity { version: String },
+
+    /// `LeafletResources::Bundled` was used without the `bundled-leaflet` feature
+    BundledLeafletDisabled,
+
+    /// The map does not exist, either not created yet or already removed
+    MapNotFound(Id),
+
+    /// The layer does not exist, either not created yet or already removed
+    LayerNotFound(Id),
+
+    /// A component was placed where it cannot work, like a `Popup` directly in a `Map` without a position
+    Misplaced(String),
+
+    /// A JavaScript function failed, with the message of the exception it threw
+    JsException { func: String, message: String },
+
+    /// A value could not be passed between Rust and JavaScript
+    Serialization(String),
+}
+
+impl fmt::Display for LeafletError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::LeafletNotLoaded => write!(f, "Leaflet could not be loaded"),
+            Self::MissingIntegrity { version } => write!(
+                f,
+                "No integrity hash known for Leaflet {version}, refusing to load it from the CDN"
+            ),
+            Self::MissingMarkerClusterIntegrity { version } => write!(
+                f,
+                "No integrity hash set for Leaflet.markercluster {version}, refusing to load it from the CDN"
+            ),
+            Self::BundledLeafletDisabled => write!(
+                f,
+                "LeafletResources::Bundled requires the bundled-leaflet feature"
+            ),
+            Self::MapNotFound(id) => write!(f, "Map {id} not found"),
+            Self::LayerNotFound(id) => write!(f, "Layer {id} not found"),
+            Self::Misplaced(message) => write!(f, "{message}"),
+            Self::JsException { func, message } => write!(f, "{func} failed: {message}"),
+            Self::Serialization(message) => write!(f, "Serialization failed: {message}"),
+        }
+    }
+}
+
+impl Error for LeafletError {}
+
+impl From<serde_json::Error> for LeafletError {
+    fn from(e: serde_json::Error) -> Self {
+        Self::Serialization(e.to_string())
+    }
+}
@@ -1,5 +1,17 @@
+#[cfg(feature = "bundled-leaflet")]
+use dioxus::prelude::*;
 use serde::{Deserialize, Serialize};
 
+use super::LeafletError;
+
+/// Leaflet's `dist` folder, copied as is so the CSS finds its images next to it
+#[cfg(feature = "bundled-leaflet")]
+const BUNDLED_LEAFLET: Asset = asset!("/assets/leaflet", AssetOptions::folder());
+
+// Hashes of the Leaflet 1.9.4 files, on the CDN and in `assets/leaflet`
+const LEAFLET_1_9_4_CSS_INTEGRITY: &str = "sha256-p4NxAoJBhIIN+hmNHrzRCf9tD/miZyoHS5obTRR9BMY=";
+const LEAFLET_1_9_4_JS_INTEGRITY: &str = "sha256-20nQCchB9co0qIjJZRGuk2/Z9VM+kNiyxNV1lvTlZBo=";
+
 /// Leaflet resource configuration
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum LeafletResources {
@@ -7,9 +19,22 @@ pub enum LeafletResources {
     Cdn {
         version: String,
         base_url: Option<String>, // Allow custom CDN base URL
+        /// Integrity hashes of the CSS and JS, known ones are used when not set
+        css_integrity: Option<String>,
+        js_integrity: Option<String>,
+        /// Refuse to load the files when their integrity hashes are unknown
+        strict: bool,
     },
     /// Use local files
-    Local { css_path: String, js_path: String },
+    Local {
+        css_path: String,
+        js_path: String,
+        css_integrity: Option<String>,
+        js_integrity: Option<String>,
+    },
+    /// Use Leaflet 1.9.4 shipped with the crate, which needs no network.
+    /// Requires the `bundled-leaflet` feature, refused by `check_integrity` without it.
+    Bundled,
 }
 
 impl LeafletResources {
@@ -18,6 +43,9 @@ impl LeafletResources {
         Self::Cdn {
             version: version.into(),
             base_url: None,
+            css_integrity: None,
+            js_integrity: None,
+            strict: false,
         }
     }
 
@@ -26,48 +54,125 @@ impl LeafletResources {
         Self::Cdn {
             version: version.into(),
             base_url: Some(base_url.into()),
+            css_integrity: None,
+            js_integrity: None,
+            strict: false,
         }
     }
 
+    /// Creates a configuration using the files shipped with the `bundled-leaflet` feature
+    #[cfg(feature = "bundled-leaflet")]
+    pub fn bundled() -> Self {
+        Self::Bundled
+    }
+
     /// Creates a local files configuration
     pub fn local(css_path: impl Into<String>, js_path: impl Into<String>) -> Self {
         Self::Local {
             css_path: css_path.into(),
             js_path: js_path.into(),
+            css_integrity: None,
+            js_integrity: None,
+        }
+    }
+
+    /// Sets the integrity hashes of the CSS and JS files, like `sha256-...`
+    pub fn with_integrity(mut self, css: impl Into<String>, js: impl Into<String>) -> Self {
+        match &mut self {
+            Self::Cdn {
+                css_integrity,
+                js_integrity,
+                ..
+            }
+            | Self::Local {
+                css_integrity,
+                js_integrity,
+                ..
+            } => {
+                *css_integrity = Some(css.into());
+                *js_integrity = Some(js.into());
+            }
+            Self::Bundled => {}
+        }
+        self
+    }
+
+    /// Refuses to load CDN files without an integrity hash, either known for their version
+    /// or set with `with_integrity`
+    pub fn strict(mut self) -> Self {
+        if let Self::Cdn { strict, .. } = &mut self {
+            *strict = true;
+        }
+        self
+    }
+
+    /// Checks that the files can be loaded, failing in strict mode when a hash is missing
+    /// and for bundled files without the `bundled-leaflet` feature
+    pub fn check_integrity(&self) -> Result<(), LeafletError> {
+        match self {
+            Self::Bundled if !cfg!(feature = "bundled-leaflet") => {
+                Err(LeafletError::BundledLeafletDisabled)
+            }
+            Self::Cdn {
+                version,
+                strict: true,
+                ..
+            } if self.css_integrity().is_none() || self.js_integrity().is_none() => {
+                Err(LeafletError::MissingIntegrity {
+                    version: version.clone(),
+                })
+            }
+            _ => Ok(()),
         }
     }
 
     /// Returns the CSS URL/path
     pub fn css_url(&self) -> String {
         match self {
-            Self::Cdn { version, base_url } => {
+            Self::Cdn {
+                version, base_url, ..
+            } => {
                 let base = base_url.as_deref().unwrap_or("https://unpkg.com");
                 format!("{}/leaflet@{}/dist/leaflet.css", base, version)
             }
             Self::Local { css_path, .. } => css_path.clone(),
+            Self::Bundled => bundled_url("leaflet.css"),
         }
     }
 
     /// Returns the JS URL/path
     pub fn js_url(&self) -> String {
         match self {
-            Self::Cdn { version, base_url } => {
+            Self::Cdn {
+                version, base_url, ..
+            } => {
                 let base = base_url.as_deref().unwrap_or("https://unpkg.com");
                 format!("{}/leaflet@{}/dist/leaflet.js", base, version)
             }
             Self::Local { js_path, .. } => js_path.clone(),
+            Self::Bundled => bundled_url("leaflet.js"),
         }
     }
 
-    /// Returns the integrity hash for CSS (if using CDN with known versions)
+    /// Returns the integrity hash for CSS (if set, or using CDN with known versions)
     pub fn css_integrity(&self) -> Option<String> {
         match self {
-            Self::Cdn { version, base_url } if base_url.is_none() => {
+            Self::Cdn {
+                css_integrity: Some(integrity),
+                ..
+            }
+            | Self::Local {
+                css_integrity: Some(integrity),
+                ..
+            } => Some(integrity.clone()),
+            // The bundled files are checked too, in case they were replaced
+            Self::Bundled => Some(LEAFLET_1_9_4_CSS_INTEGRITY.to_string()),
+            Self::Cdn {
+                version, base_url, ..
+            } if base_url.is_none() => {
                 // Only provide integrity for unpkg.com with known versions
                 match version.as_str() {
-                    "1.9.4" => {
-                        Some("sha256-p4NxAoJBhIIN+hmNHrzRCf9tD/miZyoHS5obTRR9BMY=".to_string())
-                    }
+                    "1.9.4" => Some(LEAFLET_1_9_4_CSS_INTEGRITY.to_string()),
                     "1.9.3" => {
                         Some("sha256-kLaT2GOSpHechhsozzB+flnD+zUyjE2LlfWPgU04xyI=".to_string())
                     }
@@ -81,15 +186,24 @@ impl LeafletResources {
         }
     }
 
-    /// Returns the integrity hash for JS (if using CDN with known versions)
+    /// Returns the integrity hash for JS (if set, or using CDN with known versions)
     pub fn js_integrity(&self) -> Option<String> {
         match self {
-            Self::Cdn { version, base_url } if base_url.is_none() => {
+            Self::Cdn {
+                js_integrity: Some(integrity),
+                ..
+            }
+            | Self::Local {
+                js_integrity: Some(integrity),
+                ..
+            } => Some(integrity.clone()),
+            Self::Bundled => Some(LEAFLET_1_9_4_JS_INTEGRITY.to_string()),
+            Self::Cdn {
+                version, base_url, ..
+            } if base_url.is_none() => {
                 // Only provide integrity for unpkg.com with known versions
                 match version.as_str() {
-                    "1.9.4" => {
-                        Some("sha256-20nQCchB9co0qIjJZRGuk2/Z9VM+kNiyxNV1lvTlZBo=".to_string())
-                    }
+                    "1.9.4" => Some(LEAFLET_1_9_4_JS_INTEGRITY.to_string()),
                     "1.9.3" => {
                         Some("sha256-WBkoXOwTeyKclOHuWtc+i2uENFpDZ9YPdf5Hf+D7ewM=".to_string())
                     }
@@ -104,8 +218,88 @@ impl LeafletResources {
     }
 }
 
+#[cfg(feature = "bundled-leaflet")]
+fn bundled_url(file: &str) -> String {
+    format!("{BUNDLED_LEAFLET}/{file}")
+}
+
+// Never loaded, `check_integrity` refuses bundled files without the feature
+#[cfg(not(feature = "bundled-leaflet"))]
+fn bundled_url(_file: &str) -> String {
+    String::new()
+}
+
 impl Default for LeafletResources {
     fn default() -> Self {
         Self::cdn("1.9.4")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_versions_pass_strict_checking() {
+        let resources = LeafletResources::cdn("1.9.4").strict();
+        assert_eq!(resources.check_integrity(), Ok(()));
+        assert_eq!(
+            resources.js_integrity().as_deref(),
+            Some(LEAFLET_1_9_4_JS_INTEGRITY)
+        );
+    }
+
+    #[test]
+    fn unknown_versions_only_fail_in_strict_mode() {
+        assert_eq!(LeafletResources::cdn("1.8.0").check_integrity(), Ok(()));
+        assert_eq!(
+            LeafletResources::cdn("1.8.0").strict().check_integrity(),
+            Err(LeafletError::MissingIntegrity {
+                version: "1.8.0".to_string()
+            })
+        );
+        assert_eq!(
+            LeafletResources::cdn("1.8.0")
+                .with_integrity("sha256-css", "sha256-js")
+                .strict()
+                .check_integrity(),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn custom_cdns_have_no_known_hashes() {
+        let resources = LeafletResources::cdn_with_base_url("1.9.4", "https://cdn.example.com");
+        assert_eq!(resources.css_integrity(), None);
+        assert!(resources.strict().check_integrity().is_err());
+    }
+
+    #[test]
+    fn bundled_files_need_the_feature() {
+        let result = LeafletResources::Bundled.check_integrity();
+        if cfg!(feature = "bundled-leaflet") {
+            assert_eq!(result, Ok(()));
+        } else {
+            assert_eq!(result, Err(LeafletError::BundledLeafletDisabled));
+        }
+    }
+
+    #[cfg(feature = "bundled-leaflet")]
+    #[test]
+    fn bundled_files_match_their_integrity() {
+        use base64::Engine;
+
+        let integrity = |file: &str| {
+            let path = format!("{}/assets/leaflet/{file}", env!("CARGO_MANIFEST_DIR"));
+            let content = std::fs::read(&path).unwrap_or_else(|e| panic!("{path}: {e}"));
+            let digest = ring::digest::digest(&ring::digest::SHA256, &content);
+            format!(
+                "sha256-{}",
+                base64::engine::general_purpose::STANDARD.encode(digest)
+            )
+        };
+        let resources = LeafletResources::bundled();
+        assert_eq!(resources.css_integrity(), Some(integrity("leaflet.css")));
+        assert_eq!(resources.js_integrity(), Some(integrity("leaflet.js")));
+    }
+}
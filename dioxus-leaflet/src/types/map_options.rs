@@ -1,5 +1,6 @@
-use super::{LeafletResources, TileLayer};
+use super::{Crs, LatLngBounds, LeafletResources, TileLayerOptions};
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
 
 /// Map configuration options
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -11,8 +12,45 @@ pub struct MapOptions {
     pub dragging: bool,
     pub keyboard: bool,
     pub attribution_control: bool,
-    pub tile_layer: TileLayer,
+    /// Coordinate reference system of the map, `Crs::Simple` for floor plans and other
+    /// images in pixel coordinates. Only applies when the map is created.
+    pub crs: Crs,
+    /// Whether a zoom area can be drawn with shift + drag
+    pub box_zoom: bool,
+    /// Lowest zoom level the map can be zoomed out to, defaults to the one of the tile layers
+    pub min_zoom: Option<f64>,
+    /// Highest zoom level the map can be zoomed in to, defaults to the one of the tile layers
+    pub max_zoom: Option<f64>,
+    /// Area the view is restricted to
+    pub max_bounds: Option<LatLngBounds>,
+    /// How solid `max_bounds` are when dragging, from 0.0 (bounces back) to 1.0 (cannot be left at all)
+    pub max_bounds_viscosity: f64,
+    /// Zoom levels are rounded to a multiple of this, 0.0 disables snapping
+    pub zoom_snap: f64,
+    /// Zoom change of the zoom buttons and keyboard
+    pub zoom_delta: f64,
+    /// Number of scrolled pixels that zoom by one level
+    pub wheel_px_per_zoom_level: f64,
+    /// Whether the map keeps moving after a drag
+    pub inertia: bool,
+    /// Whether the view jumps to the original copy of the world when dragged past it,
+    /// so that layers stay visible
+    pub world_copy_jump: bool,
+    /// Whether paths are rendered on a canvas instead of SVG, faster with many of them.
+    /// Only applies when the map is created.
+    pub prefer_canvas: bool,
+    /// Whether zooming is animated. Only applies when the map is created.
+    pub zoom_animation: bool,
+    /// Whether tiles fade in. Only applies when the map is created.
+    pub fade_animation: bool,
+    /// Tile layer added to the map, if any. Use the `TileLayer` component instead
+    /// to show several tile layers or switch between them.
+    pub tile_layer: Option<TileLayerOptions>,
     pub leaflet_resources: LeafletResources,
+    /// Resources tried when `leaflet_resources` did not load in time, like local files behind a CDN
+    pub leaflet_fallback: Option<LeafletResources>,
+    /// How long to wait for Leaflet to load, before trying the fallback or giving up
+    pub leaflet_timeout: Duration,
 }
 
 impl Default for MapOptions {
@@ -25,8 +63,24 @@ impl Default for MapOptions {
             dragging: true,
             keyboard: true,
             attribution_control: true,
-            tile_layer: TileLayer::default(),
+            crs: Crs::default(),
+            box_zoom: true,
+            min_zoom: None,
+            max_zoom: None,
+            max_bounds: None,
+            max_bounds_viscosity: 0.0,
+            zoom_snap: 1.0,
+            zoom_delta: 1.0,
+            wheel_px_per_zoom_level: 60.0,
+            inertia: true,
+            world_copy_jump: false,
+            prefer_canvas: false,
+            zoom_animation: true,
+            fade_animation: true,
+            tile_layer: Some(TileLayerOptions::default()),
             leaflet_resources: LeafletResources::default(),
+            leaflet_fallback: None,
+            leaflet_timeout: Duration::from_secs(10),
         }
     }
 }
@@ -42,8 +96,8 @@ impl MapOptions {
             dragging: false,
             keyboard: false,
             attribution_control: false,
-            tile_layer: TileLayer::default(),
-            leaflet_resources: LeafletResources::default(),
+            box_zoom: false,
+            ..Default::default()
         }
     }
 
@@ -89,9 +143,56 @@ impl MapOptions {
         self
     }
 
+    /// Builder method to set the coordinate reference system
+    pub fn with_crs(mut self, crs: Crs) -> Self {
+        self.crs = crs;
+        self
+    }
+
+    /// Builder method to enable/disable box zoom
+    pub fn with_box_zoom(mut self, enabled: bool) -> Self {
+        self.box_zoom = enabled;
+        self
+    }
+
+    /// Builder method to set the zoom levels the map can be zoomed to
+    pub fn with_zoom_range(mut self, min_zoom: f64, max_zoom: f64) -> Self {
+        self.min_zoom = Some(min_zoom);
+        self.max_zoom = Some(max_zoom);
+        self
+    }
+
+    /// Builder method to restrict the view to an area. With `viscosity` set to 1.0
+    /// the area cannot be dragged out of at all.
+    pub fn with_max_bounds(mut self, bounds: LatLngBounds, viscosity: f64) -> Self {
+        self.max_bounds = Some(bounds);
+        self.max_bounds_viscosity = viscosity;
+        self
+    }
+
+    /// Builder method to set zoom snapping and the zoom step of the controls,
+    /// for example 0.25 for fractional zoom
+    pub fn with_zoom_step(mut self, zoom_snap: f64, zoom_delta: f64) -> Self {
+        self.zoom_snap = zoom_snap;
+        self.zoom_delta = zoom_delta;
+        self
+    }
+
+    /// Builder method to render paths on a canvas instead of SVG
+    pub fn with_prefer_canvas(mut self, enabled: bool) -> Self {
+        self.prefer_canvas = enabled;
+        self
+    }
+
     /// Builder method to set tile layer
-    pub fn with_tile_layer(mut self, tile_layer: TileLayer) -> Self {
-        self.tile_layer = tile_layer;
+    pub fn with_tile_layer(mut self, tile_layer: TileLayerOptions) -> Self {
+        self.tile_layer = Some(tile_layer);
+        self
+    }
+
+    /// Builder method to remove the tile layer, when tile layers are added as components
+    pub fn without_tile_layer(mut self) -> Self {
+        self.tile_layer = None;
         self
     }
 
@@ -100,4 +201,41 @@ impl MapOptions {
         self.leaflet_resources = resources;
         self
     }
+
+    /// Builder method to set the Leaflet resources used when the main ones fail to load
+    pub fn with_leaflet_fallback(mut self, resources: LeafletResources) -> Self {
+        self.leaflet_fallback = Some(resources);
+        self
+    }
+
+    /// Builder method to set how long to wait for Leaflet to load
+    pub fn with_leaflet_timeout(mut self, timeout: Duration) -> Self {
+        self.leaflet_timeout = timeout;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn minimal_only_disables_controls() {
+        let minimal = MapOptions::minimal();
+        assert!(!minimal.zoom_control && !minimal.dragging && !minimal.box_zoom);
+        assert_eq!(
+            MapOptions {
+                zoom_control: true,
+                scroll_wheel_zoom: true,
+                double_click_zoom: true,
+                touch_zoom: true,
+                dragging: true,
+                keyboard: true,
+                attribution_control: true,
+                box_zoom: true,
+                ..minimal
+            },
+            MapOptions::default()
+        );
+    }
 }
@@ -0,0 +1,62 @@
+use serde::{Deserialize, Serialize};
+
+/// Marker cluster group configuration options
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MarkerClusterOptions {
+    /// Maximum radius in pixels that a cluster covers from its central marker
+    pub max_cluster_radius: u32,
+
+    /// Whether clicking a cluster at the maximum zoom spreads its markers out
+    pub spiderfy_on_max_zoom: bool,
+
+    /// Distance multiplier between spiderfied markers
+    pub spiderfy_distance_multiplier: f64,
+
+    /// Whether hovering a cluster shows the area covered by its markers
+    pub show_coverage_on_hover: bool,
+
+    /// Whether clicking a cluster zooms to its bounds
+    pub zoom_to_bounds_on_click: bool,
+
+    /// Zoom level from which markers are no longer clustered
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disable_clustering_at_zoom: Option<u32>,
+
+    /// Whether markers are added in chunks to keep the page responsive with many markers
+    pub chunked_loading: bool,
+}
+
+impl Default for MarkerClusterOptions {
+    fn default() -> Self {
+        Self {
+            max_cluster_radius: 80,
+            spiderfy_on_max_zoom: true,
+            spiderfy_distance_multiplier: 1.0,
+            show_coverage_on_hover: true,
+            zoom_to_bounds_on_click: true,
+            disable_clustering_at_zoom: None,
+            chunked_loading: false,
+        }
+    }
+}
+
+/// Icon of a marker cluster, rendered as HTML
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClusterIcon {
+    pub html: String,
+    pub class_name: Option<String>,
+    pub icon_size: Option<(u32, u32)>,
+}
+
+impl ClusterIcon {
+    /// Creates a new ClusterIcon with just its HTML content
+    pub fn new(html: impl Into<String>) -> Self {
+        Self {
+            html: html.into(),
+            class_name: None,
+            icon_size: None,
+        }
+    }
+}
@@ -0,0 +1,162 @@
+use serde::{Deserialize, Serialize};
+
+use super::LeafletError;
+
+/// Integrity hashes of the files of a Leaflet.markercluster release, like `sha256-...`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MarkerClusterIntegrity {
+    pub css: String,
+    pub default_css: String,
+    pub js: String,
+}
+
+/// Leaflet.markercluster resource configuration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum MarkerClusterResources {
+    /// Use CDN with specified version
+    Cdn {
+        version: String,
+        base_url: Option<String>,
+        /// Integrity hashes of the files, checked by the browser when set
+        integrity: Option<MarkerClusterIntegrity>,
+        /// Refuse to load the files without integrity hashes
+        strict: bool,
+    },
+    /// Use local files
+    Local {
+        css_path: String,
+        default_css_path: String,
+        js_path: String,
+        integrity: Option<MarkerClusterIntegrity>,
+    },
+}
+
+impl MarkerClusterResources {
+    /// Creates a CDN configuration with the specified version
+    pub fn cdn(version: impl Into<String>) -> Self {
+        Self::Cdn {
+            version: version.into(),
+            base_url: None,
+            integrity: None,
+            strict: false,
+        }
+    }
+
+    /// Creates a CDN configuration with custom base URL
+    pub fn cdn_with_base_url(version: impl Into<String>, base_url: impl Into<String>) -> Self {
+        Self::Cdn {
+            version: version.into(),
+            base_url: Some(base_url.into()),
+            integrity: None,
+            strict: false,
+        }
+    }
+
+    /// Creates a local files configuration
+    pub fn local(
+        css_path: impl Into<String>,
+        default_css_path: impl Into<String>,
+        js_path: impl Into<String>,
+    ) -> Self {
+        Self::Local {
+            css_path: css_path.into(),
+            default_css_path: default_css_path.into(),
+            js_path: js_path.into(),
+            integrity: None,
+        }
+    }
+
+    /// Sets the integrity hashes of the core CSS, default icons CSS and JS files, like `sha256-...`
+    pub fn with_integrity(
+        mut self,
+        css: impl Into<String>,
+        default_css: impl Into<String>,
+        js: impl Into<String>,
+    ) -> Self {
+        match &mut self {
+            Self::Cdn { integrity, .. } | Self::Local { integrity, .. } => {
+                *integrity = Some(MarkerClusterIntegrity {
+                    css: css.into(),
+                    default_css: default_css.into(),
+                    js: js.into(),
+                });
+            }
+        }
+        self
+    }
+
+    /// Refuses to load CDN files without integrity hashes set with `with_integrity`
+    pub fn strict(mut self) -> Self {
+        if let Self::Cdn { strict, .. } = &mut self {
+            *strict = true;
+        }
+        self
+    }
+
+    /// Checks that the files can be loaded, failing in strict mode when the hashes are missing
+    pub fn check_integrity(&self) -> Result<(), LeafletError> {
+        match self {
+            Self::Cdn {
+                version,
+                strict: true,
+                integrity: None,
+                ..
+            } => Err(LeafletError::MissingMarkerClusterIntegrity {
+                version: version.clone(),
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the integrity hashes of the files, if set
+    pub fn integrity(&self) -> Option<&MarkerClusterIntegrity> {
+        match self {
+            Self::Cdn { integrity, .. } | Self::Local { integrity, .. } => integrity.as_ref(),
+        }
+    }
+
+    fn cdn_url(version: &str, base_url: &Option<String>, file: &str) -> String {
+        let base = base_url.as_deref().unwrap_or("https://unpkg.com");
+        format!("{}/leaflet.markercluster@{}/dist/{}", base, version, file)
+    }
+
+    /// Returns the URL/path of the core cluster CSS
+    pub fn css_url(&self) -> String {
+        match self {
+            Self::Cdn {
+                version, base_url, ..
+            } => Self::cdn_url(version, base_url, "MarkerCluster.css"),
+            Self::Local { css_path, .. } => css_path.clone(),
+        }
+    }
+
+    /// Returns the URL/path of the CSS for the default cluster icons
+    pub fn default_css_url(&self) -> String {
+        match self {
+            Self::Cdn {
+                version, base_url, ..
+            } => Self::cdn_url(version, base_url, "MarkerCluster.Default.css"),
+            Self::Local {
+                default_css_path, ..
+            } => default_css_path.clone(),
+        }
+    }
+
+    /// Returns the JS URL/path
+    pub fn js_url(&self) -> String {
+        match self {
+            Self::Cdn {
+                version, base_url, ..
+            } => Self::cdn_url(version, base_url, "leaflet.markercluster.js"),
+            Self::Local { js_path, .. } => js_path.clone(),
+        }
+    }
+}
+
+/// Leaflet.markercluster 1.5.3 from unpkg. No hashes are built in for it, so its files
+/// are only checked by the browser once set with `with_integrity`.
+impl Default for MarkerClusterResources {
+    fn default() -> Self {
+        Self::cdn("1.5.3")
+    }
+}
@@ -1,15 +1,42 @@
 use serde::{Deserialize, Serialize};
 
+use color::{AlphaColor, Srgb};
+
 /// Custom marker icon configuration
+///
+/// Sizes are in pixels, and anchors are pixel offsets from the top left corner of the icon.
+/// Options left to `None` use Leaflet's defaults.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct MarkerIcon {
     pub icon_url: String,
+    /// Larger version of the icon used on retina screens
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon_retina_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub icon_size: Option<(u32, u32)>,
-    pub icon_anchor: Option<(u32, u32)>,
+    /// Point of the icon placed at the marker coordinate, its center by default
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon_anchor: Option<(i32, i32)>,
+    /// Point popups open from, relative to `icon_anchor`
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub popup_anchor: Option<(i32, i32)>,
+    /// Point tooltips open from, relative to `icon_anchor`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tooltip_anchor: Option<(i32, i32)>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub shadow_url: Option<String>,
+    /// Larger version of the shadow used on retina screens
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shadow_retina_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub shadow_size: Option<(u32, u32)>,
+    /// Point of the shadow placed at the marker coordinate, same as `icon_anchor` by default
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shadow_anchor: Option<(i32, i32)>,
+    /// Custom CSS class for the icon and shadow images
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub class_name: Option<String>,
 }
 
 impl MarkerIcon {
@@ -17,11 +44,125 @@ impl MarkerIcon {
     pub fn new(icon_url: impl Into<String>) -> Self {
         Self {
             icon_url: icon_url.into(),
+            icon_retina_url: None,
             icon_size: None,
             icon_anchor: None,
             popup_anchor: None,
+            tooltip_anchor: None,
             shadow_url: None,
+            shadow_retina_url: None,
             shadow_size: None,
+            shadow_anchor: None,
+            class_name: None,
         }
     }
+
+    /// Pin shaped like Leaflet's default marker, filled with the given color, whose alpha is kept.
+    /// The SVG is embedded in the URL, so it stays sharp on retina screens without extra files.
+    ///
+    /// ```rust
+    /// # use dioxus_leaflet::{Color, MarkerIcon};
+    /// let warning = MarkerIcon::colored(Color::from_rgb8(0xf3, 0x9c, 0x12));
+    /// let faded = MarkerIcon::colored(Color::from_rgb8(0xf3, 0x9c, 0x12).with_alpha(0.5));
+    /// ```
+    pub fn colored(color: impl Into<AlphaColor<Srgb>>) -> Self {
+        let color = color.into().to_rgba8();
+        let (r, g, b) = (color.r, color.g, color.b);
+        let opacity = f64::from(color.a) / 255.0;
+        // `#` must be escaped in data URLs
+        let svg = format!(
+            "<svg xmlns='http://www.w3.org/2000/svg' width='25' height='41' viewBox='0 0 25 41'>\
+             <path d='M12.5 0.5C5.9 0.5 0.5 5.9 0.5 12.5c0 9 12 28 12 28s12-19 12-28C24.5 5.9 19.1 0.5 12.5 0.5z' \
+             fill='%23{r:02x}{g:02x}{b:02x}' fill-opacity='{opacity:.3}' stroke='%23000' stroke-opacity='0.35'/>\
+             <circle cx='12.5' cy='12.5' r='4.5' fill='%23fff'/>\
+             </svg>"
+        );
+        Self::new(format!("data:image/svg+xml;charset=utf-8,{svg}"))
+            .with_size(25, 41)
+            .with_anchor(12, 41)
+            .with_popup_anchor(1, -34)
+            .with_tooltip_anchor(16, -28)
+    }
+
+    /// Builder method to set the size of the icon
+    pub fn with_size(mut self, width: u32, height: u32) -> Self {
+        self.icon_size = Some((width, height));
+        self
+    }
+
+    /// Builder method to set the point of the icon placed at the marker coordinate
+    pub fn with_anchor(mut self, x: i32, y: i32) -> Self {
+        self.icon_anchor = Some((x, y));
+        self
+    }
+
+    /// Builder method to set the point popups open from
+    pub fn with_popup_anchor(mut self, x: i32, y: i32) -> Self {
+        self.popup_anchor = Some((x, y));
+        self
+    }
+
+    /// Builder method to set the point tooltips open from
+    pub fn with_tooltip_anchor(mut self, x: i32, y: i32) -> Self {
+        self.tooltip_anchor = Some((x, y));
+        self
+    }
+
+    /// Builder method to set the icon used on retina screens
+    pub fn with_retina_url(mut self, url: impl Into<String>) -> Self {
+        self.icon_retina_url = Some(url.into());
+        self
+    }
+
+    /// Builder method to set the shadow image, its size and anchor
+    pub fn with_shadow(
+        mut self,
+        url: impl Into<String>,
+        size: (u32, u32),
+        anchor: (i32, i32),
+    ) -> Self {
+        self.shadow_url = Some(url.into());
+        self.shadow_size = Some(size);
+        self.shadow_anchor = Some(anchor);
+        self
+    }
+
+    /// Builder method to set the CSS class of the icon
+    pub fn with_class_name(mut self, class_name: impl Into<String>) -> Self {
+        self.class_name = Some(class_name.into());
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Color;
+
+    #[test]
+    fn colored_pin_fills_with_the_color() {
+        let icon = MarkerIcon::colored(Color::from_rgb8(0xf3, 0x9c, 0x12));
+        assert!(
+            icon.icon_url
+                .starts_with("data:image/svg+xml;charset=utf-8,<svg")
+        );
+        assert!(
+            icon.icon_url
+                .contains("fill='%23f39c12' fill-opacity='1.000'")
+        );
+        assert!(!icon.icon_url.contains('#'));
+        assert_eq!(icon.icon_size, Some((25, 41)));
+        assert_eq!(icon.icon_anchor, Some((12, 41)));
+        assert_eq!(icon.popup_anchor, Some((1, -34)));
+        assert_eq!(icon.tooltip_anchor, Some((16, -28)));
+    }
+
+    #[test]
+    fn colored_pin_keeps_alpha() {
+        let icon = MarkerIcon::colored(Color::from_rgb8(0, 0, 0xff).with_alpha(0.5));
+        assert!(
+            icon.icon_url
+                .contains("fill='%230000ff' fill-opacity='0.502'")
+        );
+    }
 }
@@ -1,37 +1,236 @@
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 
-/// Tile layer configuration
+use crate::LatLngBounds;
+
+const OSM_ATTRIBUTION: &str =
+    "&copy; <a href=\"https://www.openstreetmap.org/copyright\">OpenStreetMap</a> contributors";
+
+/// Tile layer configuration, named `TileLayer` up to 0.3 before the `TileLayer` component
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-pub struct TileLayer {
+pub struct TileLayerOptions {
+    /// URL template of the tiles, with `{s}`, `{z}`, `{x}`, `{y}` and `{r}` placeholders
+    /// as well as any of the `url_variables`
     pub url: String,
+
     pub attribution: String,
+
+    pub min_zoom: u8,
+
     pub max_zoom: u8,
+
+    /// Highest zoom level the provider has tiles for, tiles are upscaled above it
+    pub max_native_zoom: Option<u8>,
+
     pub subdomains: Vec<String>,
+
+    /// Width and height of the tiles in pixels
+    pub tile_size: u32,
+
+    /// Offset added to the zoom number used in tile URLs
+    pub zoom_offset: i32,
+
+    /// Whether the Y axis is inverted, for TMS services
+    pub tms: bool,
+
+    pub opacity: f64,
+
+    pub z_index: i32,
+
+    /// Whether higher zoom tiles are requested on retina screens to keep them sharp
+    pub detect_retina: bool,
+
+    /// Image shown in place of tiles that fail to load
+    pub error_tile_url: Option<String>,
+
+    /// Area outside of which no tiles are requested
+    pub bounds: Option<LatLngBounds>,
+
+    /// CORS mode of the tile requests, needed to read tiles back from a canvas
+    pub cross_origin: Option<CrossOrigin>,
+
+    /// Values of the custom placeholders in `url`, like `{apikey}`
+    pub url_variables: BTreeMap<String, String>,
 }
 
-impl TileLayer {
-    /// OpenStreetMap tile layer (default)
-    pub fn openstreetmap() -> Self {
+/// CORS mode of image requests
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CrossOrigin {
+    Anonymous,
+    UseCredentials,
+}
+
+impl TileLayerOptions {
+    /// Creates new tile layer options with the given URL template and attribution.
+    ///
+    /// The other options start from Leaflet's defaults: zoom levels 0 to 18, the `a`, `b` and `c`
+    /// subdomains for `{s}`, 256 pixel tiles and full opacity.
+    pub fn new(url: impl Into<String>, attribution: impl Into<String>) -> Self {
         Self {
-            url: "https://{s}.tile.openstreetmap.org/{z}/{x}/{y}.png".to_string(),
-            attribution: "&copy; <a href=\"https://www.openstreetmap.org/copyright\">OpenStreetMap</a> contributors".to_string(),
-            max_zoom: 19,
+            url: url.into(),
+            attribution: attribution.into(),
+            min_zoom: 0,
+            max_zoom: 18,
+            max_native_zoom: None,
             subdomains: vec!["a".to_string(), "b".to_string(), "c".to_string()],
+            tile_size: 256,
+            zoom_offset: 0,
+            tms: false,
+            opacity: 1.0,
+            z_index: 1,
+            detect_retina: false,
+            error_tile_url: None,
+            bounds: None,
+            cross_origin: None,
+            url_variables: BTreeMap::new(),
         }
     }
 
-    /// Satellite imagery tile layer
+    /// Sets the value of a custom placeholder in the URL template, like `{apikey}`
+    pub fn with_url_variable(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.url_variables.insert(name.into(), value.into());
+        self
+    }
+
+    fn with_subdomains(mut self, subdomains: &[&str]) -> Self {
+        self.subdomains = subdomains.iter().map(|s| s.to_string()).collect();
+        self
+    }
+
+    fn with_max_zoom(mut self, max_zoom: u8) -> Self {
+        self.max_zoom = max_zoom;
+        self
+    }
+
+    /// OpenStreetMap tile layer (default)
+    pub fn openstreetmap() -> Self {
+        Self::new(
+            "https://{s}.tile.openstreetmap.org/{z}/{x}/{y}.png",
+            OSM_ATTRIBUTION,
+        )
+        .with_max_zoom(19)
+    }
+
+    /// Satellite imagery tile layer, same as `esri_world_imagery`
     pub fn satellite() -> Self {
-        Self {
-            url: "https://server.arcgisonline.com/ArcGIS/rest/services/World_Imagery/MapServer/tile/{z}/{y}/{x}".to_string(),
-            attribution: "Tiles &copy; Esri".to_string(),
-            max_zoom: 18,
-            subdomains: vec![],
+        Self::esri_world_imagery()
+    }
+
+    /// OpenTopoMap topographic tile layer
+    pub fn opentopomap() -> Self {
+        Self::new(
+            "https://{s}.tile.opentopomap.org/{z}/{x}/{y}.png",
+            format!(
+                "Map data: {OSM_ATTRIBUTION}, <a href=\"http://viewfinderpanoramas.org\">SRTM</a> | Map style: &copy; <a href=\"https://opentopomap.org\">OpenTopoMap</a> (<a href=\"https://creativecommons.org/licenses/by-sa/3.0/\">CC-BY-SA</a>)"
+            ),
+        )
+        .with_max_zoom(17)
+    }
+
+    /// CARTO Positron light tile layer
+    pub fn carto_positron() -> Self {
+        Self::carto("light_all")
+    }
+
+    /// CARTO Dark Matter dark tile layer
+    pub fn carto_dark_matter() -> Self {
+        Self::carto("dark_all")
+    }
+
+    fn carto(style: &str) -> Self {
+        // `{r}` already serves high resolution tiles on retina screens
+        Self::new(
+            format!("https://{{s}}.basemaps.cartocdn.com/{style}/{{z}}/{{x}}/{{y}}{{r}}.png"),
+            format!(
+                "{OSM_ATTRIBUTION} &copy; <a href=\"https://carto.com/attributions\">CARTO</a>"
+            ),
+        )
+        .with_subdomains(&["a", "b", "c", "d"])
+        .with_max_zoom(20)
+    }
+
+    /// Stamen Toner tile layer, hosted by Stadia Maps
+    ///
+    /// Stadia Maps needs an account, either allow your domain in it and pass no API key,
+    /// or pass your API key:
+    ///
+    /// ```rust
+    /// # use dioxus_leaflet::TileLayerOptions;
+    /// let toner = TileLayerOptions::stamen_toner(Some("my-key"));
+    /// ```
+    pub fn stamen_toner(api_key: Option<&str>) -> Self {
+        Self::stamen("stamen_toner", "png", api_key)
+    }
+
+    /// Stamen Terrain tile layer, hosted by Stadia Maps, see `stamen_toner`
+    pub fn stamen_terrain(api_key: Option<&str>) -> Self {
+        Self::stamen("stamen_terrain", "png", api_key)
+    }
+
+    /// Stamen Watercolor tile layer, hosted by Stadia Maps, see `stamen_toner`
+    pub fn stamen_watercolor(api_key: Option<&str>) -> Self {
+        Self::stamen("stamen_watercolor", "jpg", api_key).with_max_zoom(16)
+    }
+
+    fn stamen(style: &str, extension: &str, api_key: Option<&str>) -> Self {
+        let layer = Self::new(
+            format!(
+                "https://tiles.stadiamaps.com/tiles/{style}/{{z}}/{{x}}/{{y}}{{r}}.{extension}"
+            ),
+            format!(
+                "&copy; <a href=\"https://www.stadiamaps.com/\">Stadia Maps</a> &copy; <a href=\"https://www.stamen.com/\">Stamen Design</a> &copy; <a href=\"https://openmaptiles.org/\">OpenMapTiles</a> {OSM_ATTRIBUTION}"
+            ),
+        )
+        .with_subdomains(&[])
+        .with_max_zoom(20);
+        match api_key {
+            Some(api_key) => Self {
+                url: format!("{}?api_key={{api_key}}", layer.url),
+                ..layer
+            }
+            .with_url_variable("api_key", api_key),
+            None => layer,
         }
     }
+
+    /// Esri World Street Map tile layer
+    pub fn esri_world_street_map() -> Self {
+        Self::esri(
+            "World_Street_Map",
+            "Source: Esri, DeLorme, NAVTEQ, USGS, Intermap, iPC, NRCAN, Esri Japan, METI, Esri China (Hong Kong), Esri (Thailand), TomTom, 2012",
+        )
+    }
+
+    /// Esri World Topographic Map tile layer
+    pub fn esri_world_topo_map() -> Self {
+        Self::esri(
+            "World_Topo_Map",
+            "Esri, DeLorme, NAVTEQ, TomTom, Intermap, iPC, USGS, FAO, NPS, NRCAN, GeoBase, Kadaster NL, Ordnance Survey, Esri Japan, METI, Esri China (Hong Kong), and the GIS User Community",
+        )
+    }
+
+    /// Esri World Imagery satellite tile layer
+    pub fn esri_world_imagery() -> Self {
+        Self::esri(
+            "World_Imagery",
+            "Source: Esri, i-cubed, USDA, USGS, AEX, GeoEye, Getmapping, Aerogrid, IGN, IGP, UPR-EGP, and the GIS User Community",
+        )
+    }
+
+    fn esri(service: &str, sources: &str) -> Self {
+        Self::new(
+            format!(
+                "https://server.arcgisonline.com/ArcGIS/rest/services/{service}/MapServer/tile/{{z}}/{{y}}/{{x}}"
+            ),
+            format!("Tiles &copy; Esri &mdash; {sources}"),
+        )
+        .with_subdomains(&[])
+    }
 }
 
-impl Default for TileLayer {
+impl Default for TileLayerOptions {
     fn default() -> Self {
         Self::openstreetmap()
     }
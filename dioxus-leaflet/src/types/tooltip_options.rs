@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+
+/// Side of the layer on which a tooltip opens
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TooltipDirection {
+    Right,
+    Left,
+    Top,
+    Bottom,
+    Center,
+    Auto,
+}
+
+/// Tooltip configuration options
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TooltipOptions {
+    /// Whether to always show the tooltip instead of only on hover
+    pub permanent: Option<bool>,
+    pub direction: Option<TooltipDirection>,
+    /// Offset of the tooltip position, in pixels
+    pub offset: Option<(i32, i32)>,
+    /// Whether the tooltip follows the mouse instead of staying at the layer anchor
+    pub sticky: Option<bool>,
+    pub opacity: f64,
+    pub class_name: Option<String>,
+}
+
+impl Default for TooltipOptions {
+    fn default() -> Self {
+        Self {
+            permanent: Some(false),
+            direction: Some(TooltipDirection::Auto),
+            offset: Some((0, 0)),
+            sticky: Some(false),
+            opacity: 0.9,
+            class_name: None,
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// WMS tile layer configuration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WmsOptions {
+    /// Comma-separated list of the WMS layers to show
+    pub layers: String,
+
+    /// Comma-separated list of the WMS styles, one per layer
+    pub styles: String,
+
+    /// Image format of the tiles, e.g. `image/png`
+    pub format: String,
+
+    /// Whether the service should return images with transparency
+    pub transparent: bool,
+
+    /// WMS service version
+    pub version: String,
+
+    /// Coordinate reference system of the requests, like `EPSG:4326`, the map's own when not set
+    pub crs: Option<String>,
+
+    pub attribution: String,
+
+    /// Additional parameters sent with every request, like `CQL_FILTER` or `TIME`
+    pub extra_params: BTreeMap<String, String>,
+}
+
+impl WmsOptions {
+    /// Creates new WmsOptions showing the given layers
+    pub fn new(layers: impl Into<String>) -> Self {
+        Self {
+            layers: layers.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Adds a parameter sent with every request
+    pub fn with_param(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.extra_params.insert(key.into(), value.into());
+        self
+    }
+}
+
+impl Default for WmsOptions {
+    fn default() -> Self {
+        Self {
+            layers: String::new(),
+            styles: String::new(),
+            format: "image/jpeg".to_string(),
+            transparent: false,
+            version: "1.1.1".to_string(),
+            crs: None,
+            attribution: String::new(),
+            extra_params: BTreeMap::new(),
+        }
+    }
+}